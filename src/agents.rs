@@ -1,9 +1,13 @@
 //! Agent-specific instruction formatting and file conventions.
 //!
-//! Each AI coding agent (Copilot, Claude, Cursor, Codex, Aider, etc.) has its own
+//! Each AI coding agent (Copilot, Claude, Cursor, Windsurf, Cline, Roo Code, Gemini
+//! CLI, Codex, Aider, etc.) has its own
 //! instruction file format, path conventions, and recommended practices. This module
 //! defines the agent abstraction and provides formatting/emission logic.
 
+use crate::cli::InitOptions;
+use crate::config::{Config, ConfigError, ProtectConfig, ResolvedRule, slugify};
+use crate::prompt_builder::split_sections;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fs;
@@ -15,6 +19,10 @@ pub enum Agent {
     Copilot,
     Claude,
     Cursor,
+    Windsurf,
+    Cline,
+    Roo,
+    Gemini,
     Codex,
     Aider,
     Raw,
@@ -27,6 +35,10 @@ impl Agent {
             Agent::Copilot,
             Agent::Claude,
             Agent::Cursor,
+            Agent::Windsurf,
+            Agent::Cline,
+            Agent::Roo,
+            Agent::Gemini,
             Agent::Codex,
             Agent::Aider,
         ]
@@ -37,6 +49,10 @@ impl Agent {
             Agent::Copilot => "copilot",
             Agent::Claude => "claude",
             Agent::Cursor => "cursor",
+            Agent::Windsurf => "windsurf",
+            Agent::Cline => "cline",
+            Agent::Roo => "roo",
+            Agent::Gemini => "gemini",
             Agent::Codex => "codex",
             Agent::Aider => "aider",
             Agent::Raw => "raw",
@@ -48,6 +64,10 @@ impl Agent {
             Agent::Copilot => "GitHub Copilot",
             Agent::Claude => "Claude Code",
             Agent::Cursor => "Cursor",
+            Agent::Windsurf => "Windsurf",
+            Agent::Cline => "Cline",
+            Agent::Roo => "Roo Code",
+            Agent::Gemini => "Gemini CLI",
             Agent::Codex => "OpenAI Codex",
             Agent::Aider => "Aider",
             Agent::Raw => "Raw",
@@ -60,6 +80,10 @@ impl Agent {
             Agent::Copilot => "GitHub Copilot — .github/copilot-instructions.md",
             Agent::Claude => "Claude Code — CLAUDE.md at project root",
             Agent::Cursor => "Cursor IDE — .cursor/rules/promptctl.mdc",
+            Agent::Windsurf => "Windsurf — .windsurf/rules/promptctl.md",
+            Agent::Cline => "Cline — .clinerules/promptctl.md",
+            Agent::Roo => "Roo Code — .roo/rules/promptctl.md",
+            Agent::Gemini => "Gemini CLI — GEMINI.md at project root",
             Agent::Codex => "OpenAI Codex — AGENTS.md at project root",
             Agent::Aider => "Aider — CONVENTIONS.md at project root",
             Agent::Raw => "Raw output — no agent wrapper",
@@ -71,6 +95,10 @@ impl Agent {
             "copilot" | "github-copilot" | "gh-copilot" => Some(Agent::Copilot),
             "claude" | "claude-code" | "anthropic" => Some(Agent::Claude),
             "cursor" => Some(Agent::Cursor),
+            "windsurf" | "codeium" => Some(Agent::Windsurf),
+            "cline" => Some(Agent::Cline),
+            "roo" | "roo-code" | "roocode" => Some(Agent::Roo),
+            "gemini" | "gemini-cli" => Some(Agent::Gemini),
             "codex" | "openai-codex" | "openai" => Some(Agent::Codex),
             "aider" => Some(Agent::Aider),
             "raw" | "none" | "generic" => Some(Agent::Raw),
@@ -83,6 +111,10 @@ impl Agent {
             Agent::Copilot => ".github/copilot-instructions.md",
             Agent::Claude => "CLAUDE.md",
             Agent::Cursor => ".cursor/rules/promptctl.mdc",
+            Agent::Windsurf => ".windsurf/rules/promptctl.md",
+            Agent::Cline => ".clinerules/promptctl.md",
+            Agent::Roo => ".roo/rules/promptctl.md",
+            Agent::Gemini => "GEMINI.md",
             Agent::Codex => "AGENTS.md",
             Agent::Aider => "CONVENTIONS.md",
             Agent::Raw => "",
@@ -94,29 +126,43 @@ impl Agent {
             Agent::Copilot => Some(".github/copilot-instructions.md"),
             Agent::Claude => Some(".claude/CLAUDE.md"),
             Agent::Cursor => Some(".cursor/rules/promptctl.mdc"),
+            Agent::Windsurf => Some(".codeium/windsurf/memories/global_rules.md"),
+            Agent::Cline => Some("Documents/Cline/Rules/promptctl.md"),
+            Agent::Roo => Some(".roo/rules/promptctl.md"),
+            Agent::Gemini => Some(".gemini/GEMINI.md"),
             _ => None,
         }
     }
 
-    #[allow(dead_code)]
     pub const fn token_budget(&self) -> usize {
         match self {
             Agent::Copilot => 4000,
             Agent::Claude => 8000,
             Agent::Cursor => 4000,
+            // Windsurf truncates rule files past 12,000 characters
+            Agent::Windsurf => 3000,
+            // Cline and Roo Code load every rule file on every request
+            Agent::Cline => 6000,
+            Agent::Roo => 6000,
+            Agent::Gemini => 8000,
             Agent::Codex => 6000,
             Agent::Aider => 4000,
             Agent::Raw => 8000,
         }
     }
 
-    pub fn format_prompt(&self, content: &str, language: &str) -> String {
+    /// Wrap content in the agent's file format. `args` are the `init` flags,
+    /// recorded in the header so regenerating keeps them.
+    pub fn format_prompt(&self, content: &str, language: &str, args: &str) -> String {
         match self {
-            Agent::Copilot => format_copilot(content, language),
-            Agent::Claude => format_claude(content, language),
-            Agent::Cursor => format_cursor(content, language),
-            Agent::Codex => format_codex(content, language),
-            Agent::Aider => format_aider(content, language),
+            Agent::Copilot => format_copilot(content, language, args),
+            Agent::Claude => format_claude(content, language, args),
+            Agent::Cursor => format_cursor(content, language, args),
+            Agent::Windsurf => format_windsurf(content, language, args),
+            Agent::Cline | Agent::Roo => format_rule_file(self, content, args),
+            Agent::Gemini => format_gemini(content, language, args),
+            Agent::Codex => format_codex(content, language, args),
+            Agent::Aider => format_aider(content, language, args),
             Agent::Raw => content.to_string(),
         }
     }
@@ -151,9 +197,24 @@ impl Agent {
         }
 
         if let Some(parent) = path.parent() {
+            // Cline also accepts `.clinerules` as a single file
+            if parent.is_file() {
+                return Err(AgentError::Io(std::io::Error::other(format!(
+                    "{} is a file — move its rules into a directory of the same name",
+                    parent.display()
+                ))));
+            }
             fs::create_dir_all(parent).map_err(AgentError::Io)?;
         }
 
+        // Windsurf's global rules are one plain Markdown file; activation
+        // frontmatter only applies to workspace rules
+        let content = if global && *self == Agent::Windsurf {
+            strip_frontmatter(content)
+        } else {
+            content
+        };
+
         fs::write(&path, content).map_err(AgentError::Io)?;
         Ok(path)
     }
@@ -171,6 +232,7 @@ pub enum AgentError {
     AlreadyExists(PathBuf),
     NoFilePath,
     NoHookSupport(Agent),
+    Config(ConfigError),
 }
 
 impl fmt::Display for AgentError {
@@ -184,6 +246,7 @@ impl fmt::Display for AgentError {
             AgentError::NoHookSupport(a) => {
                 write!(f, "agent '{}' has no native hook support (use 'emit' instead)", a.name())
             }
+            AgentError::Config(e) => write!(f, "{e}"),
         }
     }
 }
@@ -192,18 +255,21 @@ impl std::error::Error for AgentError {}
 
 // ── Hallucination prevention guardrails ──────────────────────────────────────
 
-/// Generate anti-hallucination instructions tailored to a language and agent.
-/// These are appended to every generated prompt when not in raw mode.
-pub fn hallucination_guardrails(language: &str) -> String {
-    let lang_specific = match language.to_lowercase().as_str() {
-        "rust" => RUST_GUARDRAILS,
-        "go" => GO_GUARDRAILS,
-        "typescript" | "ts" => TS_GUARDRAILS,
-        "solidity" | "sol" => SOLIDITY_GUARDRAILS,
-        "leo" => LEO_GUARDRAILS,
-        _ => "",
-    };
+/// The `--role` recorded in the header of a file generated by `init`.
+pub fn recorded_role(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .take(10)
+        .filter(|l| l.contains("promptctl init "))
+        .find_map(|l| l.split("--role ").nth(1)?.split_whitespace().next())
+        .map(str::to_string)
+}
 
+/// Generate anti-hallucination instructions: the common list plus a
+/// language's own guardrails (from its skillset).
+/// These are appended to every generated prompt when not in raw mode.
+pub fn hallucination_guardrails(lang_specific: &str) -> String {
     let mut output = String::from(COMMON_GUARDRAILS);
     if !lang_specific.is_empty() {
         output.push('\n');
@@ -224,35 +290,12 @@ const COMMON_GUARDRAILS: &str = r#"## Hallucination Prevention
 - **Prefer standard library solutions** over third-party when the stdlib provides equivalent functionality.
 - **Quote error messages exactly** when referencing compiler or runtime errors — do not paraphrase."#;
 
-const RUST_GUARDRAILS: &str = r#"- Do not reference unstable features or nightly-only APIs unless the project explicitly uses nightly.
-- Do not invent trait implementations — verify a type actually implements a trait before calling its methods.
-- Do not fabricate `unsafe` justifications — every `unsafe` block must have a real, auditable safety comment."#;
-
-const GO_GUARDRAILS: &str = r#"- Do not reference Go generics syntax from versions prior to 1.18 or features beyond the go.mod version.
-- Do not invent methods on standard library types — verify with `go doc`.
-- Do not fabricate build tags or linker flags."#;
-
-const TS_GUARDRAILS: &str = r#"- Do not invent TypeScript compiler options — verify against the tsconfig.json reference.
-- Do not reference DOM APIs in Node.js contexts or vice versa without checking the environment.
-- Do not fabricate type utility names — verify they exist in `typescript` or `@types/*` packages."#;
-
-const SOLIDITY_GUARDRAILS: &str = r#"- Assume every external caller is a malicious contract — apply adversarial mindset.
-- Enforce Checks-Effects-Interactions (CEI) pattern on every state-changing function that makes external calls.
-- Every public/external state-changing function MUST have explicit access control — missing modifiers are critical vulnerabilities.
-- Do not reference Solidity features from versions higher than the pragma specifies.
-- Do not invent EIPs or precompile addresses — verify they exist on the target chain.
-- Do not hallucinate storage slot layouts, ABI encoding details, or OpenZeppelin API surfaces."#;
-
-const LEO_GUARDRAILS: &str = r#"- Do not invent Leo/Aleo instructions, opcodes, or record fields that do not exist.
-- Do not fabricate program IDs or deployment addresses.
-- Do not reference Aleo network features that are not yet on mainnet."#;
-
 // ── Agent-specific formatters ────────────────────────────────────────────────
 
-fn format_copilot(content: &str, _language: &str) -> String {
+fn format_copilot(content: &str, _language: &str, args: &str) -> String {
     format!(
         r#"<!-- Generated by promptctl — GitHub Copilot instructions -->
-<!-- Regenerate: promptctl init copilot -->
+<!-- Regenerate: promptctl init copilot {args} --force -->
 
 <!-- COPILOT INSTRUCTIONS START -->
 {content}
@@ -261,10 +304,10 @@ fn format_copilot(content: &str, _language: &str) -> String {
     )
 }
 
-fn format_claude(content: &str, _language: &str) -> String {
+fn format_claude(content: &str, _language: &str, args: &str) -> String {
     format!(
         r#"<!-- Generated by promptctl — Claude Code instructions -->
-<!-- Regenerate: promptctl init claude -->
+<!-- Regenerate: promptctl init claude {args} --force -->
 
 <instructions>
 {content}
@@ -273,7 +316,7 @@ fn format_claude(content: &str, _language: &str) -> String {
     )
 }
 
-fn format_cursor(content: &str, language: &str) -> String {
+fn format_cursor(content: &str, language: &str, args: &str) -> String {
     // Cursor uses MDC (Markdown Components) format with YAML frontmatter.
     format!(
         r#"---
@@ -282,27 +325,73 @@ globs:
 alwaysApply: true
 ---
 
+<!-- Regenerate: promptctl init cursor {args} --force -->
+
 {content}
 "#,
         language = language,
+        args = args,
         content = content,
     )
 }
 
-fn format_codex(content: &str, _language: &str) -> String {
+fn format_windsurf(content: &str, _language: &str, args: &str) -> String {
+    // Windsurf rules carry their activation mode in YAML frontmatter.
+    format!(
+        r#"---
+trigger: always_on
+---
+
+<!-- Regenerate: promptctl init windsurf {args} --force -->
+
+{content}
+"#
+    )
+}
+
+fn format_rule_file(agent: &Agent, content: &str, args: &str) -> String {
+    // Cline and Roo Code rules are plain Markdown, always loaded.
+    format!(
+        r#"<!-- Regenerate: promptctl init {agent} {args} --force -->
+
+{content}
+"#,
+        agent = agent.name(),
+    )
+}
+
+/// `text` without its leading `---` frontmatter block, if it has one.
+fn strip_frontmatter(text: &str) -> &str {
+    text.strip_prefix("---\n")
+        .and_then(|body| body.split_once("\n---\n"))
+        .map(|(_, rest)| rest.trim_start_matches('\n'))
+        .unwrap_or(text)
+}
+
+fn format_gemini(content: &str, _language: &str, args: &str) -> String {
+    format!(
+        r#"<!-- Generated by promptctl — Gemini CLI instructions -->
+<!-- Regenerate: promptctl init gemini {args} --force -->
+
+{content}
+"#
+    )
+}
+
+fn format_codex(content: &str, _language: &str, args: &str) -> String {
     format!(
         r#"<!-- Generated by promptctl — OpenAI Codex agent instructions -->
-<!-- Regenerate: promptctl init codex -->
+<!-- Regenerate: promptctl init codex {args} --force -->
 
 {content}
 "#
     )
 }
 
-fn format_aider(content: &str, _language: &str) -> String {
+fn format_aider(content: &str, _language: &str, args: &str) -> String {
     format!(
         r#"<!-- Generated by promptctl — Aider conventions -->
-<!-- Regenerate: promptctl init aider -->
+<!-- Regenerate: promptctl init aider {args} --force -->
 
 {content}
 "#
@@ -320,6 +409,15 @@ fn format_aider(content: &str, _language: &str) -> String {
 //   • Cursor — `.cursor/rules/*.mdc` with per-language globs
 //     Rules are applied when files matching the glob are in context.
 //
+//   • Windsurf — `.windsurf/rules/*.md` with a `trigger` activation mode
+//     (`always_on`, `glob`, `model_decision`).
+//
+//   • Cline / Roo Code — `.clinerules/*.md` / `.roo/rules/*.md`, all loaded on
+//     every request; Roo also reads mode-specific `.roo/rules-<mode>/`.
+//
+//   • Gemini CLI — `.gemini/*.md` skillsets pulled into GEMINI.md by `@file`
+//     imports, plus `context.fileName` in `.gemini/settings.json`.
+//
 //   • Copilot — `.github/instructions/*.instructions.md` with `applyTo` globs
 //     Path-specific instruction files scoped to file types.
 //
@@ -327,7 +425,26 @@ fn format_aider(content: &str, _language: &str) -> String {
 
 /// Which agents support native hooks?
 pub fn supports_hooks(agent: Agent) -> bool {
-    matches!(agent, Agent::Claude | Agent::Cursor | Agent::Copilot)
+    matches!(
+        agent,
+        Agent::Claude
+            | Agent::Cursor
+            | Agent::Windsurf
+            | Agent::Cline
+            | Agent::Roo
+            | Agent::Gemini
+            | Agent::Copilot
+    )
+}
+
+/// Language skillsets `init` prepared for an agent's hook files.
+#[derive(Debug, Default)]
+pub struct Skillsets {
+    /// Skillset per language, shrunk to fit the agent's token budget
+    pub text: std::collections::HashMap<String, String>,
+    /// `promptctl` flags per language that rebuild the same skillset: the
+    /// `init` flags plus any sections dropped to fit the budget
+    pub args: std::collections::HashMap<String, String>,
 }
 
 /// Metadata about a single file written by the hooks installer.
@@ -340,20 +457,41 @@ pub struct HookFile {
 /// Install agent-native hooks for the given agent.
 ///
 /// `languages` is the set of detected project languages (lowercased).
-/// `skillsets` maps language name → pre-built skillset content (from `build_skillset`).
+/// `args` are the `promptctl` flags the skillsets were built with (`--role`,
+/// `--size`, `--section`, …); hooks that call `promptctl show` reuse them.
+/// `skillsets` holds the pre-built skillset per language (from `build_skillset`).
+/// `config` supplies project policy such as `[protect]` paths and `[dependencies]`.
+/// `init.skills` delivers Claude's skillsets as on-demand skills instead of at
+/// session start; `init.force` overwrites hook files that already exist.
 /// Returns a list of files written.
 pub fn install_agent_hooks(
     project_root: &Path,
     agent: Agent,
     languages: &[String],
-    role: &str,
-    skillsets: &std::collections::HashMap<String, String>,
-    force: bool,
+    args: &str,
+    skillsets: &Skillsets,
+    config: Option<&Config>,
+    init: &InitOptions,
 ) -> Result<Vec<HookFile>, AgentError> {
+    let force = init.force;
+    let rules = match config {
+        Some(c) => c.resolved_rules().map_err(AgentError::Config)?,
+        None => Vec::new(),
+    };
+
     match agent {
-        Agent::Claude => install_claude_hooks(project_root, role, force),
-        Agent::Cursor => install_cursor_hooks(project_root, languages, role, skillsets, force),
-        Agent::Copilot => install_copilot_hooks(project_root, languages, role, skillsets, force),
+        Agent::Claude => install_claude_hooks(project_root, languages, args, skillsets, config, &rules, init),
+        Agent::Cursor => {
+            install_cursor_hooks(project_root, languages, args, &skillsets.text, config, &rules, force)
+        }
+        Agent::Windsurf => {
+            install_windsurf_hooks(project_root, languages, args, &skillsets.text, config, &rules, force)
+        }
+        Agent::Cline | Agent::Roo => install_rule_files(project_root, agent, languages, args, &skillsets.text, force),
+        Agent::Gemini => install_gemini_hooks(project_root, languages, args, &skillsets.text, force),
+        Agent::Copilot => {
+            install_copilot_hooks(project_root, languages, args, &skillsets.text, config, &rules, force)
+        }
         _ => Err(AgentError::NoHookSupport(agent)),
     }
 }
@@ -363,13 +501,15 @@ pub fn remove_agent_hooks(project_root: &Path, agent: Agent) -> Result<Vec<PathB
     match agent {
         Agent::Claude => remove_claude_hooks(project_root),
         Agent::Cursor => remove_cursor_hooks(project_root),
+        Agent::Windsurf => remove_windsurf_hooks(project_root),
+        Agent::Cline | Agent::Roo => remove_rule_files(project_root, agent),
+        Agent::Gemini => remove_gemini_hooks(project_root),
         Agent::Copilot => remove_copilot_hooks(project_root),
         _ => Err(AgentError::NoHookSupport(agent)),
     }
 }
 
 /// List currently installed agent hooks.
-#[allow(dead_code)]
 pub fn list_agent_hooks(project_root: &Path) -> Vec<(Agent, Vec<PathBuf>)> {
     let mut results = Vec::new();
 
@@ -385,14 +525,21 @@ pub fn list_agent_hooks(project_root: &Path) -> Vec<(Agent, Vec<PathBuf>)> {
                 }
             }
         }
-        let settings = project_root.join(".claude/settings.json");
-        if settings.exists() {
-            if let Ok(content) = fs::read_to_string(&settings) {
-                if content.contains("promptctl") {
-                    files.push(settings);
+        if let Ok(entries) = fs::read_dir(project_root.join(".claude/skills")) {
+            for entry in entries.flatten() {
+                let p = entry.path().join("SKILL.md");
+                if entry.file_name().to_string_lossy().starts_with("promptctl-") && p.exists() {
+                    files.push(p);
                 }
             }
         }
+        let settings = project_root.join(".claude/settings.json");
+        if settings.exists()
+            && let Ok(content) = fs::read_to_string(&settings)
+            && content.contains("promptctl")
+        {
+            files.push(settings);
+        }
         if !files.is_empty() {
             results.push((Agent::Claude, files));
         }
@@ -415,6 +562,66 @@ pub fn list_agent_hooks(project_root: &Path) -> Vec<(Agent, Vec<PathBuf>)> {
         }
     }
 
+    // Windsurf
+    let windsurf_rules_dir = project_root.join(".windsurf/rules");
+    if windsurf_rules_dir.exists() {
+        let mut files = Vec::new();
+        if let Ok(entries) = fs::read_dir(&windsurf_rules_dir) {
+            for entry in entries.flatten() {
+                let p = entry.path();
+                if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                    files.push(p);
+                }
+            }
+        }
+        if !files.is_empty() {
+            results.push((Agent::Windsurf, files));
+        }
+    }
+
+    // Gemini CLI
+    let mut files = Vec::new();
+    if let Ok(entries) = fs::read_dir(project_root.join(".gemini")) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                files.push(p);
+            }
+        }
+    }
+    if !files.is_empty() {
+        results.push((Agent::Gemini, files));
+    }
+
+    // Cline and Roo Code, including Roo's per-mode rules
+    for agent in [Agent::Cline, Agent::Roo] {
+        let Some(rules_dir) = rule_files_dir(agent) else {
+            continue;
+        };
+        let mut files = Vec::new();
+        if let Ok(entries) = fs::read_dir(project_root.join(rules_dir)) {
+            for entry in entries.flatten() {
+                let p = entry.path();
+                if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                    files.push(p);
+                }
+            }
+        }
+        if agent == Agent::Roo
+            && let Ok(entries) = fs::read_dir(project_root.join(".roo"))
+        {
+            for entry in entries.flatten() {
+                let p = entry.path().join("promptctl.md");
+                if entry.file_name().to_string_lossy().starts_with("rules-promptctl-") && p.exists() {
+                    files.push(p);
+                }
+            }
+        }
+        if !files.is_empty() {
+            results.push((agent, files));
+        }
+    }
+
     // Copilot
     let copilot_instr_dir = project_root.join(".github/instructions");
     if copilot_instr_dir.exists() {
@@ -437,27 +644,43 @@ pub fn list_agent_hooks(project_root: &Path) -> Vec<(Agent, Vec<PathBuf>)> {
 
 // ── Claude Code hooks ────────────────────────────────────────────────────────
 
-fn install_claude_hooks(project_root: &Path, role: &str, force: bool) -> Result<Vec<HookFile>, AgentError> {
+fn install_claude_hooks(
+    project_root: &Path,
+    languages: &[String],
+    args: &str,
+    skillsets: &Skillsets,
+    config: Option<&Config>,
+    rules: &[ResolvedRule],
+    init: &InitOptions,
+) -> Result<Vec<HookFile>, AgentError> {
+    let force = init.force;
     let hooks_dir = project_root.join(".claude/hooks");
     fs::create_dir_all(&hooks_dir).map_err(AgentError::Io)?;
 
     let mut written = Vec::new();
 
-    // 1. SessionStart script — injects promptctl guidelines on session start
-    let session_script = CLAUDE_SESSION_START_SCRIPT.replace(
-        "--role developer",
-        &format!("--role {role}"),
-    );
+    // 1. Skillsets: on-demand skills, or a SessionStart script that injects
+    //    them into every session
     let session_script_path = hooks_dir.join("promptctl-session-start.sh");
-    write_hook_file(&session_script_path, &session_script, force)?;
-    make_executable(&session_script_path)?;
-    written.push(HookFile {
-        path: session_script_path,
-        description: "Injects promptctl guidelines on session start".into(),
-    });
+    if init.skills {
+        if session_script_path.exists() {
+            fs::remove_file(&session_script_path).map_err(AgentError::Io)?;
+        }
+        written.extend(install_claude_skills(project_root, args, &skillsets.text, config, force)?);
+    } else {
+        let session_script = claude_session_start_script(languages, args, &skillsets.args);
+        write_hook_file(&session_script_path, &session_script, force)?;
+        make_executable(&session_script_path)?;
+        written.push(HookFile {
+            path: session_script_path,
+            description: "Injects promptctl guidelines on session start".into(),
+        });
+    }
 
     // 2. PreToolUse script — reminds guidelines before Write/Edit
-    let pre_write_script = CLAUDE_PRE_WRITE_SCRIPT.replace("--role developer", &format!("--role {role}"));
+    let pre_write_script = CLAUDE_PRE_WRITE_SCRIPT
+        .replace("--role developer", &shell_args(args))
+        .replace("__CUSTOM_LANGUAGES__\n", &claude_custom_language_cases(config));
     let pre_write_path = hooks_dir.join("promptctl-pre-write.sh");
     write_hook_file(&pre_write_path, &pre_write_script, force)?;
     make_executable(&pre_write_path)?;
@@ -466,9 +689,99 @@ fn install_claude_hooks(project_root: &Path, role: &str, force: bool) -> Result<
         description: "Validates language guidelines before file writes".into(),
     });
 
-    // 3. Merge into .claude/settings.json
+    // 3. Protect guard — denies Write/Edit on [protect] paths
+    let protected = config.map(|c| &c.protect);
+    let guard = protected.is_some_and(|p| !p.paths.is_empty());
+    let protect_path = hooks_dir.join("promptctl-protect.sh");
+    if let Some(protected) = protected
+        && guard
+    {
+        write_hook_file(&protect_path, &claude_protect_script(protected), force)?;
+        make_executable(&protect_path)?;
+        written.push(HookFile {
+            path: protect_path,
+            description: format!(
+                "Blocks edits to {} protected path pattern{}",
+                protected.paths.len(),
+                if protected.paths.len() == 1 { "" } else { "s" }
+            ),
+        });
+    } else if protect_path.exists() {
+        fs::remove_file(&protect_path).map_err(AgentError::Io)?;
+    }
+
+    // 4. Dependency check — flags manifest edits that break [dependencies] policy
+    let deps_check = config.is_some_and(|c| c.dependencies.values().any(|p| !p.is_empty()));
+    let deps_path = hooks_dir.join("promptctl-deps.sh");
+    if deps_check {
+        write_hook_file(&deps_path, CLAUDE_DEPS_SCRIPT, force)?;
+        make_executable(&deps_path)?;
+        written.push(HookFile {
+            path: deps_path,
+            description: "Flags denied or unlisted dependencies that manifest edits add".into(),
+        });
+    } else if deps_path.exists() {
+        fs::remove_file(&deps_path).map_err(AgentError::Io)?;
+    }
+
+    // 5. Scoped rules — injects [[rules]] content before edits to matching paths
+    let rules_path = hooks_dir.join("promptctl-rules.sh");
+    if !rules.is_empty() {
+        write_hook_file(&rules_path, &claude_rules_script(rules), force)?;
+        make_executable(&rules_path)?;
+        written.push(HookFile {
+            path: rules_path,
+            description: format!(
+                "Injects {} path-scoped rule{} before matching edits",
+                rules.len(),
+                if rules.len() == 1 { "" } else { "s" }
+            ),
+        });
+    } else if rules_path.exists() {
+        fs::remove_file(&rules_path).map_err(AgentError::Io)?;
+    }
+
+    // 6. Merge into .claude/settings.json
+    let mut entries = Vec::new();
+    if !init.skills {
+        entries.push((
+            "SessionStart",
+            serde_json::json!({
+                "matcher": "startup",
+                "hooks": [{
+                    "type": "command",
+                    "command": claude_hook_command("promptctl-session-start.sh"),
+                    "statusMessage": "Loading promptctl guidelines…"
+                }]
+            }),
+        ));
+    }
+    if guard {
+        entries.push((
+            "PreToolUse",
+            claude_hook_entry("Write|Edit|MultiEdit|NotebookEdit", "promptctl-protect.sh"),
+        ));
+    }
+    entries.push(("PreToolUse", claude_hook_entry("Write|Edit", "promptctl-pre-write.sh")));
+    if !rules.is_empty() {
+        entries.push((
+            "PreToolUse",
+            claude_hook_entry("Write|Edit|MultiEdit", "promptctl-rules.sh"),
+        ));
+    }
+    if deps_check {
+        entries.push((
+            "PreToolUse",
+            claude_hook_entry("Write|Edit|MultiEdit", "promptctl-deps.sh"),
+        ));
+        entries.push((
+            "PostToolUse",
+            claude_hook_entry("Write|Edit|MultiEdit", "promptctl-deps.sh"),
+        ));
+    }
+
     let settings_path = project_root.join(".claude/settings.json");
-    let merged = merge_claude_settings(&settings_path)?;
+    let merged = merge_claude_settings(&settings_path, &entries)?;
     fs::write(&settings_path, &merged).map_err(AgentError::Io)?;
     written.push(HookFile {
         path: settings_path,
@@ -478,9 +791,28 @@ fn install_claude_hooks(project_root: &Path, role: &str, force: bool) -> Result<
     Ok(written)
 }
 
+fn claude_hook_command(script: &str) -> String {
+    format!("\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/{script}")
+}
+
+fn claude_hook_entry(matcher: &str, script: &str) -> serde_json::Value {
+    serde_json::json!({
+        "matcher": matcher,
+        "hooks": [{
+            "type": "command",
+            "command": claude_hook_command(script)
+        }]
+    })
+}
+
 /// Merge promptctl hooks into existing .claude/settings.json without clobbering
-/// user settings. We do a simple JSON-level merge.
-fn merge_claude_settings(settings_path: &Path) -> Result<String, AgentError> {
+/// user settings. We do a simple JSON-level merge: previous promptctl entries are
+/// dropped from every event, the user's own entries are kept, and `entries`
+/// (event name → hook entry) are appended.
+fn merge_claude_settings(
+    settings_path: &Path,
+    entries: &[(&str, serde_json::Value)],
+) -> Result<String, AgentError> {
     let existing: serde_json::Value = if settings_path.exists() {
         let raw = fs::read_to_string(settings_path).map_err(AgentError::Io)?;
         serde_json::from_str(&raw).unwrap_or(serde_json::Value::Object(Default::default()))
@@ -493,64 +825,45 @@ fn merge_claude_settings(settings_path: &Path) -> Result<String, AgentError> {
         _ => serde_json::Map::new(),
     };
 
-    // Build our hooks object
-    let our_hooks: serde_json::Value = serde_json::json!({
-        "hooks": {
-            "SessionStart": [{
-                "matcher": "startup",
-                "hooks": [{
-                    "type": "command",
-                    "command": "\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/promptctl-session-start.sh",
-                    "statusMessage": "Loading promptctl guidelines…"
-                }]
-            }],
-            "PreToolUse": [{
-                "matcher": "Write|Edit",
-                "hooks": [{
-                    "type": "command",
-                    "command": "\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/promptctl-pre-write.sh"
-                }]
-            }]
+    let mut hooks = match root.remove("hooks") {
+        Some(serde_json::Value::Object(m)) => m,
+        _ => serde_json::Map::new(),
+    };
+
+    // Drop previous promptctl entries, keep the user's own
+    for value in hooks.values_mut() {
+        if let serde_json::Value::Array(arr) = value {
+            arr.retain(|v| !v.to_string().contains("promptctl"));
         }
-    });
+    }
 
-    // Merge: if user already has "hooks", merge our event arrays in
-    if let Some(serde_json::Value::Object(existing_hooks)) = root.get("hooks") {
-        let mut merged_hooks = existing_hooks.clone();
-        if let serde_json::Value::Object(our) = &our_hooks["hooks"] {
-            for (event, value) in our {
-                // Only add our entry if the event doesn't already have promptctl hooks
-                if let Some(serde_json::Value::Array(existing_arr)) = merged_hooks.get(event) {
-                    let has_promptctl = existing_arr.iter().any(|v| {
-                        v.to_string().contains("promptctl")
-                    });
-                    if has_promptctl {
-                        continue; // Already installed
-                    }
-                    let mut combined = existing_arr.clone();
-                    if let serde_json::Value::Array(new_arr) = value {
-                        combined.extend(new_arr.clone());
-                    }
-                    merged_hooks.insert(event.clone(), serde_json::Value::Array(combined));
-                } else {
-                    merged_hooks.insert(event.clone(), value.clone());
-                }
-            }
+    for (event, entry) in entries {
+        let slot = hooks
+            .entry(event.to_string())
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if let serde_json::Value::Array(arr) = slot {
+            arr.push(entry.clone());
         }
-        root.insert("hooks".into(), serde_json::Value::Object(merged_hooks));
-    } else {
-        root.insert("hooks".into(), our_hooks["hooks"].clone());
     }
 
+    hooks.retain(|_, v| !matches!(v, serde_json::Value::Array(a) if a.is_empty()));
+    root.insert("hooks".into(), serde_json::Value::Object(hooks));
+
     serde_json::to_string_pretty(&serde_json::Value::Object(root))
-        .map_err(|e| AgentError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))
+        .map_err(|e| AgentError::Io(std::io::Error::other(e)))
 }
 
 fn remove_claude_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
     let mut removed = Vec::new();
 
     let hooks_dir = project_root.join(".claude/hooks");
-    for name in ["promptctl-session-start.sh", "promptctl-pre-write.sh"] {
+    for name in [
+        "promptctl-session-start.sh",
+        "promptctl-pre-write.sh",
+        "promptctl-protect.sh",
+        "promptctl-deps.sh",
+        "promptctl-rules.sh",
+    ] {
         let p = hooks_dir.join(name);
         if p.exists() {
             fs::remove_file(&p).map_err(AgentError::Io)?;
@@ -558,6 +871,30 @@ fn remove_claude_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError>
         }
     }
 
+    // Remove generated skills
+    let skills_dir = project_root.join(".claude/skills");
+    if let Ok(entries) = fs::read_dir(&skills_dir) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_dir() && p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_dir_all(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+
+    // Remove generated subagents
+    let agents_dir = project_root.join(".claude/agents");
+    if let Ok(entries) = fs::read_dir(&agents_dir) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_file(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+
     // Remove our entries from settings.json
     let settings_path = project_root.join(".claude/settings.json");
     if settings_path.exists() {
@@ -575,13 +912,13 @@ fn remove_claude_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError>
                 });
             }
             // If hooks is now empty, remove it
-            if let Some(serde_json::Value::Object(hooks)) = root.get("hooks") {
-                if hooks.is_empty() {
-                    root.remove("hooks");
-                }
+            if let Some(serde_json::Value::Object(hooks)) = root.get("hooks")
+                && hooks.is_empty()
+            {
+                root.remove("hooks");
             }
             let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(root))
-                .map_err(|e| AgentError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+                .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
             fs::write(&settings_path, pretty).map_err(AgentError::Io)?;
             removed.push(settings_path);
         }
@@ -590,112 +927,677 @@ fn remove_claude_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError>
     Ok(removed)
 }
 
-const CLAUDE_SESSION_START_SCRIPT: &str = r#"#!/bin/bash
-# promptctl — Claude Code SessionStart hook
-# Injects project-aware coding guidelines into the session context.
+// ── Claude Code skills (.claude/skills/promptctl-<lang>/) ────────────────────
 
-if ! command -v promptctl >/dev/null 2>&1; then
-  exit 0
-fi
+/// Write each language's skillset as a skill Claude loads only when working in
+/// that language. `SKILL.md` keeps the title and guardrails and links every
+/// other section, which goes to its own supporting file.
+fn install_claude_skills(
+    project_root: &Path,
+    args: &str,
+    skillsets: &std::collections::HashMap<String, String>,
+    config: Option<&Config>,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let skills_dir = project_root.join(".claude/skills");
+    let mut languages: Vec<&String> = skillsets.keys().collect();
+    languages.sort();
 
-GUIDELINES=$(promptctl show rust 2>/dev/null || promptctl list 2>/dev/null)
-if [ -z "$GUIDELINES" ]; then
-  exit 0
-fi
+    let mut written = Vec::new();
+    for lang in languages {
+        let dir = skills_dir.join(format!("promptctl-{lang}"));
+        let skill_path = dir.join("SKILL.md");
+        if skill_path.exists() && !force {
+            return Err(AgentError::AlreadyExists(skill_path));
+        }
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(AgentError::Io)?;
+        }
+        fs::create_dir_all(&dir).map_err(AgentError::Io)?;
+
+        let mut inline = String::new();
+        let mut links = String::new();
+        for (title, body) in split_sections(&skillsets[lang]) {
+            if title == "(preamble)" || title == "Hallucination Prevention" {
+                inline.push_str(body.trim_end());
+                inline.push_str("\n\n");
+                continue;
+            }
+            let file = format!("{}.md", slugify(&title));
+            fs::write(dir.join(&file), &body).map_err(AgentError::Io)?;
+            links.push_str(&format!("- [{title}]({file})\n"));
+        }
 
-# Return guidelines as additionalContext so Claude sees them
-jq -n --arg ctx "$GUIDELINES" '{
-  "hookSpecificOutput": {
-    "hookEventName": "SessionStart",
-    "additionalContext": $ctx
-  }
-}'
-"#;
+        let files = language_glob_info(lang, config)
+            .map(|info| format!(" ({} files)", info.ext))
+            .unwrap_or_default();
+        let content = format!(
+            r#"---
+name: promptctl-{lang}
+description: "{lang} coding guidelines for this project{files}. Use when writing, editing or reviewing {lang} code."
+---
 
-const CLAUDE_PRE_WRITE_SCRIPT: &str = r#"#!/bin/bash
-# promptctl — Claude Code PreToolUse hook (Write|Edit)
-# Adds a reminder of the relevant language guidelines before file writes.
+<!-- Generated by promptctl init claude {args} --skills -->
+<!-- Regenerate: promptctl init claude {args} --skills --force -->
 
-if ! command -v promptctl >/dev/null 2>&1; then
-  exit 0
-fi
+{inline}Read the guidelines relevant to the task before changing code:
 
-# Extract the file path from the tool input on stdin
-INPUT=$(cat)
-FILE_PATH=$(echo "$INPUT" | jq -r '.tool_input.file_path // empty')
+{links}"#
+        );
+        fs::write(&skill_path, content).map_err(AgentError::Io)?;
+        written.push(HookFile {
+            path: skill_path,
+            description: format!("{lang} skillset, loaded when working in {lang}"),
+        });
+    }
+    Ok(written)
+}
 
-if [ -z "$FILE_PATH" ]; then
-  exit 0
-fi
+// ── Claude Code subagents (.claude/agents/*.md) ─────────────────────────────
 
-# Detect extension → language
-EXT="${FILE_PATH##*.}"
-case "$EXT" in
-  rs)   LANG="rust" ;;
-  go)   LANG="go" ;;
-  ts|tsx) LANG="typescript" ;;
-  sol)  LANG="solidity" ;;
-  leo)  LANG="leo" ;;
-  *)    exit 0 ;;
-esac
+/// A role's prompt, generated as a subagent or a slash command.
+pub struct RolePrompt {
+    /// Role name, used as the subagent or command name
+    pub name: String,
+    /// When to use it
+    pub description: String,
+    /// The role prefix alone, for agents that already load the skillsets
+    pub prefix: String,
+    /// The role prefix plus the detected languages' skillsets
+    pub body: String,
+    /// The single roles it is made of (`Persona::roles`), which decide its tools
+    pub roles: Vec<String>,
+}
 
-# Fetch a minimal reminder for the target language
-REMINDER=$(promptctl show "$LANG" --role developer 2>/dev/null | head -30)
-if [ -z "$REMINDER" ]; then
-  exit 0
-fi
+/// How much a role may change, from least to most. Reviewers and auditors
+/// only read, so they cannot change what they are judging. Performance work
+/// edits and runs code, but has no use for the web.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ToolAccess {
+    ReadOnly,
+    Docs,
+    NoBrowse,
+    Full,
+}
 
-jq -n --arg ctx "Guidelines reminder for $LANG: follow the project coding standards." '{
-  "hookSpecificOutput": {
-    "hookEventName": "PreToolUse",
-    "additionalContext": $ctx
-  }
-}'
-"#;
+impl ToolAccess {
+    /// The most restrictive access of the roles a persona is made of, so
+    /// `senior+security` or a custom role combining `security` only reads.
+    fn of(roles: &[String]) -> Self {
+        roles
+            .iter()
+            .map(|role| match role.as_str() {
+                "reviewer" | "security" => Self::ReadOnly,
+                "documentation" => Self::Docs,
+                "performance" => Self::NoBrowse,
+                _ => Self::Full,
+            })
+            .min()
+            .unwrap_or(Self::Full)
+    }
+}
 
-// ── Cursor hooks (per-language .mdc rules) ───────────────────────────────────
+/// Tools a role's subagent may use; `None` inherits every tool.
+fn subagent_tools(roles: &[String]) -> Option<&'static str> {
+    match ToolAccess::of(roles) {
+        ToolAccess::ReadOnly => Some("Read, Grep, Glob"),
+        ToolAccess::Docs => Some("Read, Grep, Glob, Edit, Write"),
+        ToolAccess::NoBrowse => Some("Read, Grep, Glob, Bash, Edit, Write"),
+        ToolAccess::Full => None,
+    }
+}
 
-fn install_cursor_hooks(
+/// Write one `.claude/agents/promptctl-<role>.md` file per subagent.
+pub fn install_claude_subagents(
     project_root: &Path,
-    languages: &[String],
-    role: &str,
-    skillsets: &std::collections::HashMap<String, String>,
+    subagents: &[RolePrompt],
+    args: &str,
     force: bool,
 ) -> Result<Vec<HookFile>, AgentError> {
-    let rules_dir = project_root.join(".cursor/rules");
-    fs::create_dir_all(&rules_dir).map_err(AgentError::Io)?;
+    let agents_dir = project_root.join(".claude/agents");
+    fs::create_dir_all(&agents_dir).map_err(AgentError::Io)?;
 
     let mut written = Vec::new();
-
-    for lang in languages {
-        let Some(info) = cursor_language_info(lang) else {
-            continue;
-        };
-
-        let skillset_content = skillsets
-            .get(lang.as_str())
-            .map(|s| s.as_str())
-            .unwrap_or("<!-- No skillset available — run 'promptctl show' to check -->");
-
-        let filename = format!("promptctl-{lang}.mdc");
-        let path = rules_dir.join(&filename);
-
+    for subagent in subagents {
+        let path = agents_dir.join(format!("promptctl-{}.md", subagent.name));
+        let tools = subagent_tools(&subagent.roles)
+            .map(|t| format!("tools: {t}\n"))
+            .unwrap_or_default();
         let content = format!(
             r#"---
-description: "{lang} coding guidelines from promptctl — applied when editing {ext} files"
+name: {name}
+description: "{description}"
+{tools}---
+
+<!-- Generated by promptctl init claude {args} -->
+<!-- Regenerate: promptctl init claude {args} --force -->
+
+{body}
+"#,
+            name = subagent.name,
+            description = subagent.description.replace('"', "'"),
+            tools = tools,
+            args = args,
+            body = subagent.body.trim_end(),
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: match subagent_tools(&subagent.roles) {
+                Some(tools) => format!("{} subagent ({tools})", subagent.name),
+                None => format!("{} subagent (all tools)", subagent.name),
+            },
+        });
+    }
+    Ok(written)
+}
+
+// ── Role commands (slash commands / prompt files) ───────────────────────────
+//
+// One on-demand command per role, so a task can get a different persona than
+// the project-wide one:
+//
+//   • Claude Code — `.claude/commands/promptctl-<role>.md`, run as `/promptctl-<role> <task>`
+//   • Copilot — `.github/prompts/promptctl-<role>.prompt.md`, run as `/promptctl-<role>`
+//   • Cursor — `.cursor/commands/promptctl-<role>.md`, run as `/promptctl-<role> <task>`
+
+/// Which agents support role commands?
+pub fn supports_commands(agent: Agent) -> bool {
+    commands_dir(agent).is_some()
+}
+
+/// Directory an agent reads commands from, relative to the project root.
+fn commands_dir(agent: Agent) -> Option<&'static str> {
+    match agent {
+        Agent::Claude => Some(".claude/commands"),
+        Agent::Copilot => Some(".github/prompts"),
+        Agent::Cursor => Some(".cursor/commands"),
+        _ => None,
+    }
+}
+
+/// Path of a role's command file, or `None` if the agent has no commands.
+pub fn command_path(project_root: &Path, agent: Agent, role: &str) -> Option<PathBuf> {
+    let ext = if agent == Agent::Copilot { "prompt.md" } else { "md" };
+    let dir = project_root.join(commands_dir(agent)?);
+    Some(dir.join(format!("promptctl-{role}.{ext}")))
+}
+
+/// Write one command file per role prompt.
+pub fn install_role_commands(
+    project_root: &Path,
+    agent: Agent,
+    prompts: &[RolePrompt],
+    args: &str,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let mut written = Vec::new();
+    for prompt in prompts {
+        let Some(path) = command_path(project_root, agent, &prompt.name) else {
+            continue;
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(AgentError::Io)?;
+        }
+        let description = prompt.description.replace('"', "'");
+        let header = format!(
+            "<!-- Generated by promptctl init {agent} {args} -->\n\
+             <!-- Regenerate: promptctl init {agent} {args} --force -->",
+            agent = agent.name()
+        );
+        let body = prompt.body.trim_end();
+        let content = match agent {
+            Agent::Claude => format!(
+                r#"---
+description: "{description}"
+argument-hint: "[task]"
+---
+
+{header}
+
+{body}
+
+## Task
+
+$ARGUMENTS
+"#
+            ),
+            Agent::Copilot => format!(
+                r#"---
+description: "{description}"
+mode: agent
+---
+
+{header}
+
+{body}
+
+## Task
+
+${{input:task:Task for the {name} role}}
+"#,
+                name = prompt.name
+            ),
+            _ => format!(
+                r#"{header}
+
+{body}
+
+## Task
+
+Carry out the request that follows this command in the role above.
+"#
+            ),
+        };
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("/promptctl-{} command", prompt.name),
+        });
+    }
+    Ok(written)
+}
+
+/// Remove the command files promptctl generated for an agent.
+pub fn remove_role_commands(project_root: &Path, agent: Agent) -> Result<Vec<PathBuf>, AgentError> {
+    let mut removed = Vec::new();
+    let Some(dir) = commands_dir(agent) else {
+        return Ok(removed);
+    };
+    if let Ok(entries) = fs::read_dir(project_root.join(dir)) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_file(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// SessionStart script showing each detected language's skillset with the
+/// flags given to `init`, or the language's own flags in `lang_args`.
+fn claude_session_start_script(
+    languages: &[String],
+    args: &str,
+    lang_args: &std::collections::HashMap<String, String>,
+) -> String {
+    let calls: String = languages
+        .iter()
+        .map(|lang| {
+            let args = shell_args(lang_args.get(lang).map_or(args, String::as_str));
+            format!(
+                "GUIDELINES+=$(promptctl show {} {args} 2>/dev/null)$'\\n\\n'\n",
+                shell_quote(lang)
+            )
+        })
+        .collect();
+    CLAUDE_SESSION_START_SCRIPT.replace("__SHOW_CALLS__\n", &calls)
+}
+
+const CLAUDE_SESSION_START_SCRIPT: &str = r#"#!/bin/bash
+# promptctl — Claude Code SessionStart hook
+# Injects project-aware coding guidelines into the session context.
+
+if ! command -v promptctl >/dev/null 2>&1; then
+  exit 0
+fi
+
+GUIDELINES=""
+__SHOW_CALLS__
+if [ -z "${GUIDELINES//[$'\n']/}" ]; then
+  exit 0
+fi
+
+# Return guidelines as additionalContext so Claude sees them
+jq -n --arg ctx "$GUIDELINES" '{
+  "hookSpecificOutput": {
+    "hookEventName": "SessionStart",
+    "additionalContext": $ctx
+  }
+}'
+"#;
+
+const CLAUDE_PRE_WRITE_SCRIPT: &str = r#"#!/bin/bash
+# promptctl — Claude Code PreToolUse hook (Write|Edit)
+# Adds a reminder of the relevant language guidelines before file writes.
+
+if ! command -v promptctl >/dev/null 2>&1; then
+  exit 0
+fi
+
+# Extract the file path from the tool input on stdin
+INPUT=$(cat)
+FILE_PATH=$(echo "$INPUT" | jq -r '.tool_input.file_path // empty')
+
+if [ -z "$FILE_PATH" ]; then
+  exit 0
+fi
+
+# Detect extension → language
+EXT="${FILE_PATH##*.}"
+case "$EXT" in
+__CUSTOM_LANGUAGES__
+  rs)   LANG="rust" ;;
+  go)   LANG="go" ;;
+  ts|tsx) LANG="typescript" ;;
+  sol)  LANG="solidity" ;;
+  leo)  LANG="leo" ;;
+  *)    exit 0 ;;
+esac
+
+# Fetch a minimal reminder for the target language
+REMINDER=$(promptctl show "$LANG" --role developer 2>/dev/null | head -30)
+if [ -z "$REMINDER" ]; then
+  exit 0
+fi
+
+jq -n --arg ctx "Guidelines reminder for $LANG: follow the project coding standards." '{
+  "hookSpecificOutput": {
+    "hookEventName": "PreToolUse",
+    "additionalContext": $ctx
+  }
+}'
+"#;
+
+/// `case` arms mapping custom language extensions, placed before the built-ins.
+fn claude_custom_language_cases(config: Option<&Config>) -> String {
+    let Some(config) = config else {
+        return String::new();
+    };
+    let mut langs: Vec<_> = config.prompts.iter().collect();
+    langs.sort_by_key(|(lang, _)| lang.as_str());
+
+    let mut out = String::new();
+    for (lang, prompt) in langs {
+        let exts = prompt.normalized_extensions();
+        if !exts.is_empty() {
+            out.push_str(&format!("  {})  LANG=\"{lang}\" ;;\n", exts.join("|")));
+        }
+    }
+    out
+}
+
+/// Build the PreToolUse guard that denies Write/Edit on protected paths.
+///
+/// Globs become bash `case` patterns, where `*` already matches across `/`.
+/// Patterns without a slash match the file name at any depth, as in gitignore.
+fn claude_protect_script(protected: &ProtectConfig) -> String {
+    let patterns: Vec<String> = protected
+        .paths
+        .iter()
+        .flat_map(|g| glob_to_case_patterns(g))
+        .collect();
+
+    let reason = protected
+        .reason
+        .as_deref()
+        .unwrap_or("Generated or tool-managed file — change its source or generator instead of editing it by hand.")
+        .replace('\'', "'\\''");
+
+    CLAUDE_PROTECT_SCRIPT
+        .replace("__PATTERNS__", &patterns.join("|"))
+        .replace("__REASON__", &reason)
+}
+
+/// Convert a gitignore-style glob into bash `case` patterns.
+/// `{a,b}` alternatives become one pattern each, since `case` does not expand braces.
+fn glob_to_case_patterns(glob: &str) -> Vec<String> {
+    let glob = glob.trim().trim_start_matches("./");
+    if glob.is_empty() {
+        return Vec::new();
+    }
+    if let Some(open) = glob.find('{')
+        && let Some(len) = glob[open..].find('}')
+        && glob[open..open + len].contains(',')
+    {
+        let (head, tail) = (&glob[..open], &glob[open + len + 1..]);
+        let mut patterns: Vec<String> = Vec::new();
+        for alt in glob[open + 1..open + len].split(',') {
+            for p in glob_to_case_patterns(&format!("{head}{alt}{tail}")) {
+                if !patterns.contains(&p) {
+                    patterns.push(p);
+                }
+            }
+        }
+        return patterns;
+    }
+    let mut pat = glob.replace("**/", "*").replace("**", "*");
+    if pat.ends_with('/') {
+        pat.push('*');
+    }
+    let pat = quote_case_pattern(&pat);
+    if let Some(rest) = glob.strip_prefix("**/") {
+        // `**/` also matches zero directories
+        let mut patterns = vec![pat];
+        for p in glob_to_case_patterns(rest) {
+            if !patterns.contains(&p) {
+                patterns.push(p);
+            }
+        }
+        patterns
+    } else if glob.contains('/') {
+        vec![pat]
+    } else {
+        vec![format!("*/{pat}"), pat]
+    }
+}
+
+/// Quote a `case` pattern so only its `*`, `?` and `[...]` wildcards stay
+/// active; every other shell metacharacter is matched literally.
+fn quote_case_pattern(pat: &str) -> String {
+    let mut out = String::new();
+    let mut literal = String::new();
+    let mut chars = pat.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '?' => {
+                out.push_str(&quote_literal(&std::mem::take(&mut literal)));
+                out.push(c);
+            }
+            '[' if chars.clone().any(|c| c == ']') => {
+                out.push_str(&quote_literal(&std::mem::take(&mut literal)));
+                out.push('[');
+                if let Some(&neg) = chars.peek()
+                    && (neg == '!' || neg == '^')
+                {
+                    out.push(neg);
+                    chars.next();
+                }
+                let class: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                out.push_str(&quote_literal(&class));
+                out.push(']');
+            }
+            _ => literal.push(c),
+        }
+    }
+    out.push_str(&quote_literal(&literal));
+    out
+}
+
+fn quote_literal(text: &str) -> String {
+    if text.is_empty() { String::new() } else { shell_quote(text) }
+}
+
+const CLAUDE_PROTECT_SCRIPT: &str = r#"#!/bin/bash
+# promptctl — Claude Code PreToolUse hook (Write|Edit|MultiEdit|NotebookEdit)
+# Denies edits to protected paths declared in .promptctl.toml [protect].
+# Regenerate: promptctl init claude --force
+
+INPUT=$(cat)
+FILE_PATH=$(echo "$INPUT" | jq -r '.tool_input.file_path // .tool_input.notebook_path // empty')
+
+if [ -z "$FILE_PATH" ]; then
+  exit 0
+fi
+
+# Match against the path relative to the project root
+REL="${FILE_PATH#"$CLAUDE_PROJECT_DIR"/}"
+REL="${REL#./}"
+
+case "$REL" in
+  __PATTERNS__)
+    jq -n --arg path "$REL" --arg reason '__REASON__' '{
+      "hookSpecificOutput": {
+        "hookEventName": "PreToolUse",
+        "permissionDecision": "deny",
+        "permissionDecisionReason": ($path + " is protected by .promptctl.toml [protect]. " + $reason)
+      }
+    }'
+    ;;
+esac
+
+exit 0
+"#;
+
+/// Build the PreToolUse hook that injects `[[rules]]` for the edited path.
+fn claude_rules_script(rules: &[ResolvedRule]) -> String {
+    let mut cases = String::new();
+    for rule in rules {
+        let patterns = glob_to_case_patterns(&rule.glob);
+        if patterns.is_empty() {
+            continue;
+        }
+        let text = format!("### {} (`{}`)\n\n{}", rule.title, rule.glob, rule.content);
+        // The heredoc ends at the first line equal to its delimiter
+        let mut delimiter = String::from("PROMPTCTL_RULE");
+        while text.lines().any(|l| l == delimiter) {
+            delimiter.push('_');
+        }
+        cases.push_str(&format!(
+            "case \"$REL\" in\n  {})\n    CONTEXT+=$(cat <<'{delimiter}'\n{text}\n\n{delimiter}\n)$'\\n\\n'\n    ;;\nesac\n\n",
+            patterns.join("|"),
+        ));
+    }
+    CLAUDE_RULES_SCRIPT.replace("__CASES__", cases.trim_end())
+}
+
+const CLAUDE_RULES_SCRIPT: &str = r#"#!/bin/bash
+# promptctl — Claude Code PreToolUse hook (Write|Edit|MultiEdit)
+# Injects path-scoped rules from .promptctl.toml [[rules]] before matching edits.
+# Regenerate: promptctl init claude --force
+
+INPUT=$(cat)
+FILE_PATH=$(echo "$INPUT" | jq -r '.tool_input.file_path // empty')
+
+if [ -z "$FILE_PATH" ]; then
+  exit 0
+fi
+
+REL="${FILE_PATH#"$CLAUDE_PROJECT_DIR"/}"
+REL="${REL#./}"
+CONTEXT=""
+
+__CASES__
+
+if [ -z "$CONTEXT" ]; then
+  exit 0
+fi
+
+jq -n --arg ctx "Project rules for $REL:"$'\n\n'"$CONTEXT" '{
+  "hookSpecificOutput": {
+    "hookEventName": "PreToolUse",
+    "additionalContext": $ctx
+  }
+}'
+"#;
+
+const CLAUDE_DEPS_SCRIPT: &str = r#"#!/bin/bash
+# promptctl — Claude Code PreToolUse and PostToolUse hook (Write|Edit|MultiEdit)
+# Checks dependencies an edit adds to a manifest against .promptctl.toml
+# [dependencies]. Before the edit the manifest is copied aside, so the ones
+# already declared are not reported.
+
+if ! command -v promptctl >/dev/null 2>&1; then
+  exit 0
+fi
+
+INPUT=$(cat)
+FILE_PATH=$(echo "$INPUT" | jq -r '.tool_input.file_path // empty')
+EVENT=$(echo "$INPUT" | jq -r '.hook_event_name // empty')
+SESSION=$(echo "$INPUT" | jq -r '.session_id // "default"')
+
+case "$(basename "$FILE_PATH")" in
+  Cargo.toml|package.json|go.mod) ;;
+  *) exit 0 ;;
+esac
+
+SNAPSHOT="${TMPDIR:-/tmp}/promptctl-deps-$SESSION-$(printf '%s' "$FILE_PATH" | cksum | cut -d' ' -f1)"
+
+if [ "$EVENT" = "PreToolUse" ]; then
+  if [ -f "$FILE_PATH" ]; then
+    cp "$FILE_PATH" "$SNAPSHOT"
+  else
+    : > "$SNAPSHOT"
+  fi
+  exit 0
+fi
+
+# No snapshot means the PreToolUse half did not see this edit
+if [ ! -f "$SNAPSHOT" ]; then
+  exit 0
+fi
+
+REPORT=$(cd "$CLAUDE_PROJECT_DIR" && NO_COLOR=1 promptctl check-deps --base "$SNAPSHOT" "$FILE_PATH" 2>&1)
+STATUS=$?
+rm -f "$SNAPSHOT"
+if [ $STATUS -eq 0 ]; then
+  exit 0
+fi
+
+# Feed the violations back to Claude so it reverts or justifies the change
+jq -n --arg reason "$REPORT" '{
+  "decision": "block",
+  "reason": $reason
+}'
+"#;
+
+// ── Cursor hooks (per-language .mdc rules) ───────────────────────────────────
+
+fn install_cursor_hooks(
+    project_root: &Path,
+    languages: &[String],
+    args: &str,
+    skillsets: &std::collections::HashMap<String, String>,
+    config: Option<&Config>,
+    rules: &[ResolvedRule],
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let rules_dir = project_root.join(".cursor/rules");
+    fs::create_dir_all(&rules_dir).map_err(AgentError::Io)?;
+
+    let mut written = Vec::new();
+
+    for lang in languages {
+        let Some(info) = language_glob_info(lang, config) else {
+            continue;
+        };
+
+        let skillset_content = skillsets
+            .get(lang.as_str())
+            .map(|s| s.as_str())
+            .unwrap_or("<!-- No skillset available — run 'promptctl show' to check -->");
+
+        let filename = format!("promptctl-{lang}.mdc");
+        let path = rules_dir.join(&filename);
+
+        let content = format!(
+            r#"---
+description: "{lang} coding guidelines from promptctl — applied when editing {ext} files"
 globs: "{globs}"
 alwaysApply: false
 ---
 
-<!-- Generated by promptctl init cursor --role {role} -->
-<!-- Regenerate: promptctl init cursor --role {role} --force -->
+<!-- Generated by promptctl init cursor {args} -->
+<!-- Regenerate: promptctl init cursor {args} --force -->
 
 {skillset_content}
 "#,
             lang = lang,
             ext = info.ext,
             globs = info.globs,
-            role = role,
+            args = args,
             skillset_content = skillset_content,
         );
 
@@ -706,6 +1608,33 @@ alwaysApply: false
         });
     }
 
+    for rule in rules {
+        let path = rules_dir.join(format!("promptctl-rule-{}.mdc", rule.slug()));
+        let content = format!(
+            r#"---
+description: {description}
+globs: {globs}
+alwaysApply: false
+---
+
+<!-- Generated by promptctl init cursor from .promptctl.toml [[rules]] -->
+
+# {title}
+
+{content}
+"#,
+            description = yaml_quote(&format!("{} — project rule from promptctl for {}", rule.title, rule.glob)),
+            globs = yaml_quote(&rule.glob),
+            title = rule.title,
+            content = rule.content,
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("\"{}\" rule for {}", rule.title, rule.glob),
+        });
+    }
+
     if written.is_empty() {
         return Err(AgentError::Io(std::io::Error::new(
             std::io::ErrorKind::NotFound,
@@ -719,43 +1648,502 @@ alwaysApply: false
 fn remove_cursor_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
     let rules_dir = project_root.join(".cursor/rules");
     let mut removed = Vec::new();
-    if rules_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&rules_dir) {
-            for entry in entries.flatten() {
-                let p = entry.path();
-                if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
-                    fs::remove_file(&p).map_err(AgentError::Io)?;
-                    removed.push(p);
-                }
+    if rules_dir.exists()
+        && let Ok(entries) = fs::read_dir(&rules_dir)
+    {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_file(&p).map_err(AgentError::Io)?;
+                removed.push(p);
             }
         }
     }
     Ok(removed)
 }
 
-struct LangGlobInfo {
-    globs: &'static str,
-    ext: &'static str,
+// ── Windsurf hooks (per-language .md rules) ──────────────────────────────────
+
+fn install_windsurf_hooks(
+    project_root: &Path,
+    languages: &[String],
+    args: &str,
+    skillsets: &std::collections::HashMap<String, String>,
+    config: Option<&Config>,
+    rules: &[ResolvedRule],
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let rules_dir = project_root.join(".windsurf/rules");
+    fs::create_dir_all(&rules_dir).map_err(AgentError::Io)?;
+
+    let mut written = Vec::new();
+
+    for lang in languages {
+        let Some(skillset_content) = skillsets.get(lang.as_str()) else {
+            continue;
+        };
+
+        // Languages without known file globs are left to the model to apply
+        let (activation, description) = match language_glob_info(lang, config) {
+            Some(info) => (
+                format!("trigger: glob\nglobs: {}", info.globs),
+                format!("{lang} skillset for {} files", info.ext),
+            ),
+            None => (
+                format!(
+                    "trigger: model_decision\ndescription: {lang} coding guidelines — apply when writing or reviewing {lang} code"
+                ),
+                format!("{lang} skillset, applied by model decision"),
+            ),
+        };
+
+        let path = rules_dir.join(format!("promptctl-{lang}.md"));
+        let content = format!(
+            r#"---
+{activation}
+---
+
+<!-- Generated by promptctl init windsurf {args} -->
+<!-- Regenerate: promptctl init windsurf {args} --force -->
+
+{skillset_content}
+"#
+        );
+
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile { path, description });
+    }
+
+    for rule in rules {
+        let path = rules_dir.join(format!("promptctl-rule-{}.md", rule.slug()));
+        let content = format!(
+            r#"---
+trigger: glob
+globs: {globs}
+---
+
+<!-- Generated by promptctl init windsurf from .promptctl.toml [[rules]] -->
+
+# {title}
+
+{content}
+"#,
+            title = rule.title,
+            globs = yaml_quote(&rule.glob),
+            content = rule.content,
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("\"{}\" rule for {}", rule.title, rule.glob),
+        });
+    }
+
+    if written.is_empty() {
+        return Err(AgentError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no supported languages detected — run from a project directory",
+        )));
+    }
+
+    Ok(written)
+}
+
+fn remove_windsurf_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
+    let rules_dir = project_root.join(".windsurf/rules");
+    let mut removed = Vec::new();
+    if rules_dir.exists()
+        && let Ok(entries) = fs::read_dir(&rules_dir)
+    {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_file(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+    Ok(removed)
 }
 
-fn cursor_language_info(lang: &str) -> Option<LangGlobInfo> {
-    match lang.to_lowercase().as_str() {
-        "rust" => Some(LangGlobInfo { globs: "**/*.rs", ext: ".rs" }),
-        "go" => Some(LangGlobInfo { globs: "**/*.go", ext: ".go" }),
-        "typescript" | "ts" => Some(LangGlobInfo { globs: "**/*.ts,**/*.tsx", ext: ".ts/.tsx" }),
-        "solidity" | "sol" => Some(LangGlobInfo { globs: "**/*.sol", ext: ".sol" }),
-        "leo" => Some(LangGlobInfo { globs: "**/*.leo", ext: ".leo" }),
+// ── Cline / Roo Code hooks (always-loaded .md rules) ─────────────────────────
+
+/// Directory of an agent's always-loaded rule files, relative to the project root.
+fn rule_files_dir(agent: Agent) -> Option<&'static str> {
+    match agent {
+        Agent::Cline => Some(".clinerules"),
+        Agent::Roo => Some(".roo/rules"),
         _ => None,
     }
 }
 
+/// Write each language's skillset as its own rule file next to the base rule.
+/// Neither agent scopes rules by path, so `[[rules]]` stay in the base file.
+fn install_rule_files(
+    project_root: &Path,
+    agent: Agent,
+    languages: &[String],
+    args: &str,
+    skillsets: &std::collections::HashMap<String, String>,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let rules_dir = project_root.join(rule_files_dir(agent).ok_or(AgentError::NoHookSupport(agent))?);
+    fs::create_dir_all(&rules_dir).map_err(AgentError::Io)?;
+
+    let mut written = Vec::new();
+    for lang in languages {
+        let Some(skillset_content) = skillsets.get(lang.as_str()) else {
+            continue;
+        };
+        let path = rules_dir.join(format!("promptctl-{lang}.md"));
+        let content = format!(
+            r#"<!-- Generated by promptctl init {agent} {args} -->
+<!-- Regenerate: promptctl init {agent} {args} --force -->
+
+{skillset_content}
+"#,
+            agent = agent.name(),
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("{lang} skillset"),
+        });
+    }
+
+    if written.is_empty() {
+        return Err(AgentError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no supported languages detected — run from a project directory",
+        )));
+    }
+
+    Ok(written)
+}
+
+fn remove_rule_files(project_root: &Path, agent: Agent) -> Result<Vec<PathBuf>, AgentError> {
+    let mut removed = Vec::new();
+    let Some(rules_dir) = rule_files_dir(agent) else {
+        return Ok(removed);
+    };
+    if let Ok(entries) = fs::read_dir(project_root.join(rules_dir)) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_file(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+    if agent == Agent::Roo {
+        removed.extend(remove_roo_modes(project_root)?);
+    }
+    Ok(removed)
+}
+
+// ── Gemini CLI hooks (.gemini/*.md imported by GEMINI.md) ────────────────────
+
+/// `@file` imports that pull the skillsets into GEMINI.md. Imports resolve
+/// relative to the importing file: the project's GEMINI.md sits above
+/// `.gemini/`, the global `~/.gemini/GEMINI.md` next to the skillsets.
+pub fn gemini_imports(languages: &[&String], global: bool) -> String {
+    if languages.is_empty() {
+        return String::new();
+    }
+    let dir = if global { "" } else { ".gemini/" };
+    let mut imports = String::from("## Imported Skillsets\n\n");
+    for lang in languages {
+        imports.push_str(&format!("@{dir}promptctl-{lang}.md\n"));
+    }
+    imports.push('\n');
+    imports
+}
+
+fn install_gemini_hooks(
+    project_root: &Path,
+    languages: &[String],
+    args: &str,
+    skillsets: &std::collections::HashMap<String, String>,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let gemini_dir = project_root.join(".gemini");
+    fs::create_dir_all(&gemini_dir).map_err(AgentError::Io)?;
+
+    let mut written = Vec::new();
+    for lang in languages {
+        let Some(skillset_content) = skillsets.get(lang.as_str()) else {
+            continue;
+        };
+        let path = gemini_dir.join(format!("promptctl-{lang}.md"));
+        let content = format!(
+            r#"<!-- Generated by promptctl init gemini {args} -->
+<!-- Regenerate: promptctl init gemini {args} --force -->
+
+{skillset_content}
+"#
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("{lang} skillset, imported by GEMINI.md"),
+        });
+    }
+
+    if written.is_empty() {
+        return Err(AgentError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no supported languages detected — run from a project directory",
+        )));
+    }
+
+    Ok(written)
+}
+
+/// Context files Gemini CLI should load: promptctl's GEMINI.md, then
+/// instruction files shared with other agents when the project has them.
+const GEMINI_CONTEXT_FILES: &[&str] = &["GEMINI.md", "AGENTS.md"];
+
+/// Make sure `context.fileName` in `.gemini/settings.json` includes GEMINI.md
+/// and the other context files present in `root`, keeping the user's own
+/// entries and settings.
+pub fn install_gemini_settings(root: &Path) -> Result<Vec<HookFile>, AgentError> {
+    let settings_path = root.join(".gemini/settings.json");
+    let mut settings = read_json_object(&settings_path, "JSON (comments?)")?;
+
+    let mut context = match settings.remove("context") {
+        Some(serde_json::Value::Object(m)) => m,
+        _ => serde_json::Map::new(),
+    };
+    let mut names = match context.remove("fileName") {
+        Some(serde_json::Value::Array(arr)) => arr,
+        Some(name @ serde_json::Value::String(_)) => vec![name],
+        _ => Vec::new(),
+    };
+    let mut added = Vec::new();
+    for (i, name) in GEMINI_CONTEXT_FILES.iter().enumerate() {
+        if (i == 0 || root.join(name).exists()) && !names.iter().any(|n| n == name) {
+            names.push((*name).into());
+        }
+        if names.iter().any(|n| n == name) {
+            added.push(*name);
+        }
+    }
+    context.insert("fileName".into(), serde_json::Value::Array(names));
+    settings.insert("context".into(), serde_json::Value::Object(context));
+
+    fs::create_dir_all(root.join(".gemini")).map_err(AgentError::Io)?;
+    let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(settings))
+        .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+    fs::write(&settings_path, pretty).map_err(AgentError::Io)?;
+
+    Ok(vec![HookFile {
+        path: settings_path,
+        description: format!("Gemini CLI context files: {}", added.join(", ")),
+    }])
+}
+
+/// Remove the skillsets, and `context.fileName` if it names only the context
+/// files promptctl adds. A list with other files stays as the user configured it.
+fn remove_gemini_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
+    let mut removed = Vec::new();
+    if let Ok(entries) = fs::read_dir(project_root.join(".gemini")) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
+                fs::remove_file(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+
+    let settings_path = project_root.join(".gemini/settings.json");
+    if let Ok(mut settings) = read_json_object(&settings_path, "JSON")
+        && let Some(serde_json::Value::Object(context)) = settings.get_mut("context")
+        && let Some(serde_json::Value::Array(names)) = context.get("fileName")
+        && names.iter().all(|n| n.as_str().is_some_and(|n| GEMINI_CONTEXT_FILES.contains(&n)))
+    {
+        context.remove("fileName");
+        if context.is_empty() {
+            settings.remove("context");
+        }
+        if settings.is_empty() {
+            fs::remove_file(&settings_path).map_err(AgentError::Io)?;
+        } else {
+            let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(settings))
+                .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+            fs::write(&settings_path, pretty).map_err(AgentError::Io)?;
+        }
+        removed.push(settings_path);
+    }
+
+    Ok(removed)
+}
+
+// ── Roo Code custom modes (.roomodes + .roo/rules-<mode>/) ───────────────────
+
+/// Tool groups a role's Roo mode may use. Mirrors `subagent_tools`: reviewers
+/// and auditors only read, and documentation may only edit docs.
+fn roo_mode_groups(roles: &[String]) -> serde_json::Value {
+    match ToolAccess::of(roles) {
+        ToolAccess::ReadOnly => serde_json::json!(["read"]),
+        ToolAccess::Docs => serde_json::json!([
+            "read",
+            ["edit", { "fileRegex": "\\.(md|mdx|rst|txt)$", "description": "Documentation files" }]
+        ]),
+        ToolAccess::NoBrowse => serde_json::json!(["read", "edit", "command"]),
+        ToolAccess::Full => serde_json::json!(["read", "edit", "command", "browser", "mcp"]),
+    }
+}
+
+/// Add a `promptctl-<role>` custom mode per role to `.roomodes`, keeping the
+/// user's own modes, and write each role's prefix to `.roo/rules-<slug>/`.
+/// The skillsets are already in `.roo/rules/`, which loads in every mode.
+///
+/// Roo Code also accepts YAML in `.roomodes`; a file that is not JSON is left
+/// untouched and reported as an error rather than rewritten.
+pub fn install_roo_modes(
+    project_root: &Path,
+    prompts: &[RolePrompt],
+    args: &str,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let modes_path = project_root.join(".roomodes");
+    let mut root = read_json_object(&modes_path, "JSON (YAML?)")?;
+    let mut modes = match root.remove("customModes") {
+        Some(serde_json::Value::Array(arr)) => arr,
+        _ => Vec::new(),
+    };
+    modes.retain(|m| !is_promptctl_mode(m));
+
+    let mut written = Vec::new();
+    for prompt in prompts {
+        let slug = format!("promptctl-{}", prompt.name);
+        let dir = project_root.join(format!(".roo/rules-{slug}"));
+        fs::create_dir_all(&dir).map_err(AgentError::Io)?;
+        let path = dir.join("promptctl.md");
+        let content = format!(
+            r#"<!-- Generated by promptctl init roo {args} -->
+<!-- Regenerate: promptctl init roo {args} --force -->
+
+{prefix}
+"#,
+            prefix = prompt.prefix.trim(),
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("{} mode rules", prompt.name),
+        });
+
+        modes.push(serde_json::json!({
+            "slug": slug,
+            "name": format!("{} (promptctl)", prompt.name),
+            "roleDefinition": format!("You work on this project as the {} role: {}.", prompt.name, prompt.description),
+            "whenToUse": format!("Use for tasks that call for the {} role.", prompt.name),
+            "groups": roo_mode_groups(&prompt.roles),
+        }));
+    }
+
+    root.insert("customModes".into(), serde_json::Value::Array(modes));
+    let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(root))
+        .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+    fs::write(&modes_path, pretty).map_err(AgentError::Io)?;
+    written.push(HookFile {
+        path: modes_path,
+        description: "Roo Code custom modes, one per role".into(),
+    });
+
+    Ok(written)
+}
+
+fn is_promptctl_mode(mode: &serde_json::Value) -> bool {
+    mode.get("slug")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|s| s.starts_with("promptctl-"))
+}
+
+/// Remove promptctl's modes from `.roomodes` and their rule directories.
+fn remove_roo_modes(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
+    let mut removed = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(project_root.join(".roo")) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_dir() && entry.file_name().to_string_lossy().starts_with("rules-promptctl-") {
+                fs::remove_dir_all(&p).map_err(AgentError::Io)?;
+                removed.push(p);
+            }
+        }
+    }
+
+    let modes_path = project_root.join(".roomodes");
+    if let Ok(mut root) = read_json_object(&modes_path, "JSON")
+        && let Some(serde_json::Value::Array(modes)) = root.get_mut("customModes")
+        && modes.iter().any(is_promptctl_mode)
+    {
+        modes.retain(|m| !is_promptctl_mode(m));
+        // Drop the file if promptctl created it and nothing else is left
+        if modes.is_empty() && root.len() == 1 {
+            fs::remove_file(&modes_path).map_err(AgentError::Io)?;
+        } else {
+            let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(root))
+                .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+            fs::write(&modes_path, pretty).map_err(AgentError::Io)?;
+        }
+        removed.push(modes_path);
+    }
+
+    Ok(removed)
+}
+
+struct LangGlobInfo {
+    globs: String,
+    ext: String,
+}
+
+/// Hook globs for a language: custom `extensions`/`globs` from config win over
+/// the built-in table.
+fn language_glob_info(lang: &str, config: Option<&Config>) -> Option<LangGlobInfo> {
+    let lang = lang.to_lowercase();
+    if let Some(custom) = config.and_then(|c| c.get_prompt(&lang)) {
+        let globs = custom.file_globs();
+        if !globs.is_empty() {
+            let exts = custom.normalized_extensions();
+            let ext = if exts.is_empty() {
+                globs.join(", ")
+            } else {
+                exts.iter().map(|e| format!(".{e}")).collect::<Vec<_>>().join("/")
+            };
+            return Some(LangGlobInfo {
+                globs: globs.join(","),
+                ext,
+            });
+        }
+    }
+
+    let (globs, ext) = match lang.as_str() {
+        "rust" => ("**/*.rs", ".rs"),
+        "go" => ("**/*.go", ".go"),
+        "typescript" | "ts" => ("**/*.ts,**/*.tsx", ".ts/.tsx"),
+        "solidity" | "sol" => ("**/*.sol", ".sol"),
+        "leo" => ("**/*.leo", ".leo"),
+        _ => return None,
+    };
+    Some(LangGlobInfo {
+        globs: globs.to_string(),
+        ext: ext.to_string(),
+    })
+}
+
 // ── Copilot hooks (path-specific .instructions.md) ───────────────────────────
 
 fn install_copilot_hooks(
     project_root: &Path,
     languages: &[String],
-    role: &str,
+    args: &str,
     skillsets: &std::collections::HashMap<String, String>,
+    config: Option<&Config>,
+    rules: &[ResolvedRule],
     force: bool,
 ) -> Result<Vec<HookFile>, AgentError> {
     let instr_dir = project_root.join(".github/instructions");
@@ -764,7 +2152,7 @@ fn install_copilot_hooks(
     let mut written = Vec::new();
 
     for lang in languages {
-        let Some(info) = cursor_language_info(lang) else {
+        let Some(info) = language_glob_info(lang, config) else {
             continue;
         };
 
@@ -782,8 +2170,8 @@ applyTo: "{globs}"
 ---
 
 <!-- COPILOT INSTRUCTIONS START — {lang} skillset -->
-<!-- Generated by promptctl init copilot --role {role} -->
-<!-- Regenerate: promptctl init copilot --role {role} --force -->
+<!-- Generated by promptctl init copilot {args} -->
+<!-- Regenerate: promptctl init copilot {args} --force -->
 
 {skillset_content}
 
@@ -791,7 +2179,7 @@ applyTo: "{globs}"
 "#,
             globs = info.globs,
             lang = lang,
-            role = role,
+            args = args,
             skillset_content = skillset_content,
         );
 
@@ -802,6 +2190,30 @@ applyTo: "{globs}"
         });
     }
 
+    for rule in rules {
+        let path = instr_dir.join(format!("promptctl-rule-{}.instructions.md", rule.slug()));
+        let content = format!(
+            r#"---
+applyTo: {apply_to}
+---
+
+<!-- Generated by promptctl init copilot from .promptctl.toml [[rules]] -->
+
+# {title}
+
+{content}
+"#,
+            title = rule.title,
+            apply_to = yaml_quote(&rule.glob),
+            content = rule.content,
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("\"{}\" rule for {}", rule.title, rule.glob),
+        });
+    }
+
     if written.is_empty() {
         return Err(AgentError::Io(std::io::Error::new(
             std::io::ErrorKind::NotFound,
@@ -813,10 +2225,9 @@ applyTo: "{globs}"
 }
 
 fn remove_copilot_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
-    let instr_dir = project_root.join(".github/instructions");
     let mut removed = Vec::new();
-    if instr_dir.exists() {
-        if let Ok(entries) = fs::read_dir(&instr_dir) {
+    for dir in [".github/instructions", ".github/chatmodes"] {
+        if let Ok(entries) = fs::read_dir(project_root.join(dir)) {
             for entry in entries.flatten() {
                 let p = entry.path();
                 if p.file_name().is_some_and(|n| n.to_string_lossy().starts_with("promptctl-")) {
@@ -826,11 +2237,673 @@ fn remove_copilot_hooks(project_root: &Path) -> Result<Vec<PathBuf>, AgentError>
             }
         }
     }
+    removed.extend(remove_vscode_settings(project_root)?);
+    Ok(removed)
+}
+
+// ── Copilot chat modes and VS Code settings ──────────────────────────────────
+
+/// Commit message guidance that VS Code's Copilot commit generation reads
+const COPILOT_COMMIT_INSTRUCTIONS_FILE: &str = ".github/promptctl-commit-messages.md";
+
+/// The reviewer role's prompt, without chat mode frontmatter, for Copilot's
+/// review of a selection
+const COPILOT_REVIEW_INSTRUCTIONS_FILE: &str = ".github/promptctl-review.md";
+
+/// Settings promptctl added to `.vscode/settings.json` because the user had
+/// not set them; `promptctl clean` removes exactly these
+const VSCODE_SETTINGS_RECORD: &str = ".vscode/promptctl-settings.txt";
+
+const VSCODE_USE_INSTRUCTION_FILES: &str = "github.copilot.chat.codeGeneration.useInstructionFiles";
+
+const COPILOT_COMMIT_INSTRUCTIONS: &str = r#"# Commit Messages
+
+- Subject line in the imperative mood, under 72 characters, without a trailing period.
+- Say what the change does; add a short body explaining why when the subject is not enough.
+- Describe one logical change; call out breaking changes explicitly.
+- Never include secrets, tokens, or the contents of sensitive files.
+"#;
+
+/// Chat tools a role's chat mode may use; `None` keeps every tool. Mirrors
+/// `subagent_tools`: reviewers and auditors only read.
+fn chat_mode_tools(roles: &[String]) -> Option<&'static str> {
+    match ToolAccess::of(roles) {
+        ToolAccess::ReadOnly => Some("['codebase', 'search', 'usages', 'problems', 'changes']"),
+        ToolAccess::Docs => Some("['codebase', 'search', 'usages', 'editFiles']"),
+        ToolAccess::NoBrowse | ToolAccess::Full => None,
+    }
+}
+
+/// Write one `.github/chatmodes/promptctl-<role>.chatmode.md` file per role.
+pub fn install_copilot_chat_modes(
+    project_root: &Path,
+    prompts: &[RolePrompt],
+    args: &str,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let modes_dir = project_root.join(".github/chatmodes");
+    fs::create_dir_all(&modes_dir).map_err(AgentError::Io)?;
+
+    let mut written = Vec::new();
+    for prompt in prompts {
+        let path = modes_dir.join(format!("promptctl-{}.chatmode.md", prompt.name));
+        let tools = chat_mode_tools(&prompt.roles)
+            .map(|t| format!("tools: {t}\n"))
+            .unwrap_or_default();
+        let content = format!(
+            r#"---
+description: "{description}"
+{tools}---
+
+<!-- Generated by promptctl init copilot {args} -->
+<!-- Regenerate: promptctl init copilot {args} --force -->
+
+{body}
+"#,
+            description = prompt.description.replace('"', "'"),
+            body = prompt.body.trim_end(),
+        );
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: format!("{} chat mode", prompt.name),
+        });
+    }
+    Ok(written)
+}
+
+/// Point Copilot's commit message and review-selection instructions at
+/// promptctl files in `.vscode/settings.json`, keeping the user's settings.
+/// The review instructions are the `reviewer` role's prompt.
+///
+/// VS Code allows comments in settings files; one that is not plain JSON is
+/// left untouched and reported as an error rather than rewritten.
+pub fn install_vscode_settings(
+    project_root: &Path,
+    prompts: &[RolePrompt],
+    args: &str,
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let settings_path = project_root.join(".vscode/settings.json");
+    let mut root = read_json_object(&settings_path, "JSON (comments?)")?;
+    let header = format!("<!-- Generated by promptctl init copilot {args} -->");
+
+    let mut written = Vec::new();
+    let mut instructions = vec![(
+        "github.copilot.chat.commitMessageGeneration.instructions",
+        COPILOT_COMMIT_INSTRUCTIONS_FILE,
+        format!("{header}\n\n{COPILOT_COMMIT_INSTRUCTIONS}"),
+        "Copilot commit message instructions",
+    )];
+    if let Some(reviewer) = prompts.iter().find(|p| p.name == "reviewer") {
+        instructions.push((
+            "github.copilot.chat.reviewSelection.instructions",
+            COPILOT_REVIEW_INSTRUCTIONS_FILE,
+            format!("{header}\n\n{}\n", reviewer.body.trim_end()),
+            "Copilot review instructions (reviewer role)",
+        ));
+    }
+    for (key, file, content, description) in instructions {
+        let path = project_root.join(file);
+        write_hook_file(&path, &content, force)?;
+        written.push(HookFile {
+            path,
+            description: description.into(),
+        });
+
+        let mut entries = match root.remove(key) {
+            Some(serde_json::Value::Array(arr)) => arr,
+            _ => Vec::new(),
+        };
+        entries.retain(|v| !v.to_string().contains("promptctl"));
+        entries.push(serde_json::json!({ "file": file }));
+        root.insert(key.into(), serde_json::Value::Array(entries));
+    }
+
+    // Remembered as promptctl's only when the user had not set it
+    let record = project_root.join(VSCODE_SETTINGS_RECORD);
+    let mut added = read_record(&record);
+    if !root.contains_key(VSCODE_USE_INSTRUCTION_FILES) {
+        root.insert(VSCODE_USE_INSTRUCTION_FILES.into(), serde_json::Value::Bool(true));
+        added.push(VSCODE_USE_INSTRUCTION_FILES.to_string());
+    }
+
+    fs::create_dir_all(project_root.join(".vscode")).map_err(AgentError::Io)?;
+    let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(root))
+        .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+    fs::write(&settings_path, pretty).map_err(AgentError::Io)?;
+    if !added.is_empty() {
+        added.sort();
+        added.dedup();
+        let content = format!(
+            "# promptctl — settings it added to settings.json; used by `promptctl clean`\n{}\n",
+            added.join("\n")
+        );
+        fs::write(&record, content).map_err(AgentError::Io)?;
+    }
+
+    written.push(HookFile {
+        path: settings_path,
+        description: "VS Code settings for commit messages and review".into(),
+    });
+    Ok(written)
+}
+
+/// A JSON object file shared with the user, or an empty object if it is
+/// missing. `expected` names the format in the error for files that do not parse.
+fn read_json_object(
+    path: &Path,
+    expected: &str,
+) -> Result<serde_json::Map<String, serde_json::Value>, AgentError> {
+    if !path.exists() {
+        return Ok(serde_json::Map::new());
+    }
+    let raw = fs::read_to_string(path).map_err(AgentError::Io)?;
+    if raw.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    match serde_json::from_str(&raw) {
+        Ok(serde_json::Value::Object(m)) => Ok(m),
+        _ => Err(AgentError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{} is not plain {expected} — left unchanged", path.display()),
+        ))),
+    }
+}
+
+/// Remove promptctl's entries from `.vscode/settings.json` and its
+/// instruction files. Settings the user had set before `init` stay.
+fn remove_vscode_settings(project_root: &Path) -> Result<Vec<PathBuf>, AgentError> {
+    let mut removed = Vec::new();
+
+    for file in [COPILOT_COMMIT_INSTRUCTIONS_FILE, COPILOT_REVIEW_INSTRUCTIONS_FILE] {
+        let path = project_root.join(file);
+        if path.exists() {
+            fs::remove_file(&path).map_err(AgentError::Io)?;
+            removed.push(path);
+        }
+    }
+
+    let record = project_root.join(VSCODE_SETTINGS_RECORD);
+    let added = read_record(&record);
+    if record.exists() {
+        fs::remove_file(&record).map_err(AgentError::Io)?;
+    }
+
+    let settings_path = project_root.join(".vscode/settings.json");
+    if let Ok(mut root) = read_json_object(&settings_path, "JSON")
+        && (root.values().any(|v| v.to_string().contains("promptctl"))
+            || added.iter().any(|key| root.contains_key(key)))
+    {
+        root.retain(|key, _| !added.contains(key));
+        root.retain(|_, value| {
+            let serde_json::Value::Array(arr) = value else {
+                return true;
+            };
+            let had_entries = !arr.is_empty();
+            arr.retain(|v| !v.to_string().contains("promptctl"));
+            !(had_entries && arr.is_empty())
+        });
+        if root.is_empty() {
+            fs::remove_file(&settings_path).map_err(AgentError::Io)?;
+        } else {
+            let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(root))
+                .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+            fs::write(&settings_path, pretty).map_err(AgentError::Io)?;
+        }
+        removed.push(settings_path);
+    }
+
+    Ok(removed)
+}
+
+// ── Codex scoped rules (nested AGENTS.md) ────────────────────────────────────
+//
+// Codex reads AGENTS.md files hierarchically, so a rule whose glob starts with
+// a directory (`migrations/**`) becomes `migrations/AGENTS.md`. Rules that
+// cannot be scoped this way fall back to the base instruction file.
+
+const CODEX_SCOPED_MARKER: &str = "<!-- Generated by promptctl — scoped rules";
+
+/// Directory a rule's glob is rooted in, if it starts with an existing literal path.
+fn rule_directory(project_root: &Path, glob: &str) -> Option<PathBuf> {
+    let literal: Vec<&str> = glob
+        .trim_start_matches("./")
+        .split('/')
+        .take_while(|c| !c.contains(['*', '?', '[', '{']))
+        .collect();
+    // A glob without wildcards names a file; its parent is the directory.
+    let dir_parts = if literal.len() == glob.trim_start_matches("./").split('/').count() {
+        &literal[..literal.len().saturating_sub(1)]
+    } else {
+        &literal[..]
+    };
+    let dir: PathBuf = dir_parts.iter().filter(|c| !c.is_empty()).collect();
+    let full = project_root.join(&dir);
+    (!dir.as_os_str().is_empty() && full.is_dir()).then_some(full)
+}
+
+/// Rules the agent cannot scope natively; these are rendered into its base file.
+pub fn unscoped_rules<'a>(
+    project_root: &Path,
+    agent: Agent,
+    rules: &'a [ResolvedRule],
+) -> Vec<&'a ResolvedRule> {
+    match agent {
+        Agent::Claude | Agent::Cursor | Agent::Windsurf | Agent::Copilot => Vec::new(),
+        Agent::Codex => rules
+            .iter()
+            .filter(|r| rule_directory(project_root, &r.glob).is_none())
+            .collect(),
+        _ => rules.iter().collect(),
+    }
+}
+
+/// Nested AGENTS.md files for rules rooted in a directory: path, content
+/// and the number of rules in each.
+pub fn codex_scoped_files(project_root: &Path, rules: &[ResolvedRule]) -> Vec<(PathBuf, String, usize)> {
+    let mut by_dir: Vec<(PathBuf, Vec<&ResolvedRule>)> = Vec::new();
+    for rule in rules {
+        let Some(dir) = rule_directory(project_root, &rule.glob) else {
+            continue;
+        };
+        match by_dir.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, list)) => list.push(rule),
+            None => by_dir.push((dir, vec![rule])),
+        }
+    }
+
+    by_dir
+        .into_iter()
+        .map(|(dir, dir_rules)| {
+            let rel = dir.strip_prefix(project_root).unwrap_or(&dir).display().to_string();
+            let mut content = format!(
+                "{CODEX_SCOPED_MARKER} for {rel}/ -->\n<!-- Regenerate: promptctl init codex -->\n"
+            );
+            for rule in &dir_rules {
+                content.push_str(&format!(
+                    "\n## {} (`{}`)\n\n{}\n",
+                    rule.title, rule.glob, rule.content
+                ));
+            }
+            (dir.join("AGENTS.md"), content, dir_rules.len())
+        })
+        .collect()
+}
+
+/// Write nested AGENTS.md files for rules rooted in a directory.
+pub fn install_codex_scoped_rules(
+    project_root: &Path,
+    rules: &[ResolvedRule],
+    force: bool,
+) -> Result<Vec<HookFile>, AgentError> {
+    let mut written = Vec::new();
+    for (path, content, count) in codex_scoped_files(project_root, rules) {
+        // Never overwrite a hand-written AGENTS.md, even with --force
+        if path.exists()
+            && !fs::read_to_string(&path)
+                .unwrap_or_default()
+                .starts_with(CODEX_SCOPED_MARKER)
+        {
+            return Err(AgentError::AlreadyExists(path));
+        }
+        write_hook_file(&path, &content, force)?;
+        let dir = path.parent().unwrap_or(project_root);
+        let rel = dir.strip_prefix(project_root).unwrap_or(dir).display().to_string();
+        written.push(HookFile {
+            path,
+            description: format!(
+                "{count} scoped rule{} for {rel}/",
+                if count == 1 { "" } else { "s" }
+            ),
+        });
+    }
+    Ok(written)
+}
+
+/// Remove nested AGENTS.md files that promptctl generated for scoped rules.
+pub fn remove_codex_scoped_rules(
+    project_root: &Path,
+    rules: &[ResolvedRule],
+) -> Result<Vec<PathBuf>, AgentError> {
+    let mut removed = Vec::new();
+    for rule in rules {
+        let Some(dir) = rule_directory(project_root, &rule.glob) else {
+            continue;
+        };
+        let path = dir.join("AGENTS.md");
+        if removed.contains(&path) || !path.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(AgentError::Io)?;
+        if content.starts_with(CODEX_SCOPED_MARKER) {
+            fs::remove_file(&path).map_err(AgentError::Io)?;
+            removed.push(path);
+        }
+    }
     Ok(removed)
 }
 
+// ── Secret exclusions ────────────────────────────────────────────────────────
+//
+// Files the indexer classifies as sensitive (`.env`, private keys, keystores)
+// are excluded from agent context using each agent's native mechanism:
+//
+//   • Claude Code — `permissions.deny` Read rules in `.claude/settings.json`
+//   • Cursor      — `.cursorignore`
+//   • Aider       — `.aiderignore`
+//
+// Ignore files get a marked block so user entries around it are preserved.
+
+const IGNORE_BLOCK_START: &str = "# >>> promptctl sensitive files >>>";
+const IGNORE_BLOCK_END: &str = "# <<< promptctl sensitive files <<<";
+
+/// Which agents have a native way to keep files out of their context?
+pub fn supports_secret_exclusions(agent: Agent) -> bool {
+    matches!(
+        agent,
+        Agent::Claude
+            | Agent::Cursor
+            | Agent::Windsurf
+            | Agent::Cline
+            | Agent::Roo
+            | Agent::Gemini
+            | Agent::Aider
+    )
+}
+
+/// Path of the file that holds an agent's secret exclusions.
+pub fn secret_exclusions_file(project_root: &Path, agent: Agent) -> Option<PathBuf> {
+    match agent {
+        Agent::Claude => Some(project_root.join(".claude/settings.json")),
+        Agent::Cursor => Some(project_root.join(".cursorignore")),
+        Agent::Windsurf => Some(project_root.join(".codeiumignore")),
+        Agent::Cline => Some(project_root.join(".clineignore")),
+        Agent::Roo => Some(project_root.join(".rooignore")),
+        Agent::Gemini => Some(project_root.join(".geminiignore")),
+        Agent::Aider => Some(project_root.join(".aiderignore")),
+        _ => None,
+    }
+}
+
+/// Write agent-native exclusions for the given root-relative sensitive paths.
+pub fn install_secret_exclusions(
+    project_root: &Path,
+    agent: Agent,
+    paths: &[String],
+) -> Result<Vec<HookFile>, AgentError> {
+    let Some(target) = secret_exclusions_file(project_root, agent) else {
+        return Ok(Vec::new());
+    };
+
+    match agent {
+        Agent::Claude => {
+            let record = project_root.join(CLAUDE_DENY_RECORD);
+            if paths.is_empty() && !record.exists() {
+                return Ok(Vec::new());
+            }
+            let merged = merge_claude_deny_rules(&target, &read_record(&record), paths)?;
+            write_hook_file(&target, &merged, true)?;
+            write_deny_record(&record, paths)?;
+        }
+        _ => {
+            let existing = fs::read_to_string(&target).unwrap_or_default();
+            let mut content = strip_ignore_block(&existing);
+            if paths.is_empty() {
+                // Drop a block left from files that have since been deleted
+                if !existing.contains(IGNORE_BLOCK_START) {
+                    return Ok(Vec::new());
+                }
+                if content.trim().is_empty() {
+                    fs::remove_file(&target).map_err(AgentError::Io)?;
+                } else {
+                    write_hook_file(&target, &content, true)?;
+                }
+                return Ok(vec![HookFile {
+                    path: target,
+                    description: "No sensitive files left — removed promptctl's exclusions".into(),
+                }]);
+            }
+            if !content.is_empty() && !content.ends_with("\n\n") {
+                content.push('\n');
+            }
+            content.push_str(IGNORE_BLOCK_START);
+            content.push('\n');
+            for p in paths {
+                content.push_str(p);
+                content.push('\n');
+            }
+            content.push_str(IGNORE_BLOCK_END);
+            content.push('\n');
+            write_hook_file(&target, &content, true)?;
+        }
+    }
+
+    if paths.is_empty() {
+        return Ok(vec![HookFile {
+            path: target,
+            description: "No sensitive files left — removed promptctl's deny rules".into(),
+        }]);
+    }
+
+    Ok(vec![HookFile {
+        path: target,
+        description: format!(
+            "Excludes {} sensitive file{} from agent context",
+            paths.len(),
+            if paths.len() == 1 { "" } else { "s" }
+        ),
+    }])
+}
+
+/// Remove promptctl's secret exclusions. For Claude, the deny rules recorded at
+/// install time are dropped; `paths` is only used for installs made before the
+/// record existed.
+pub fn remove_secret_exclusions(
+    project_root: &Path,
+    agent: Agent,
+    paths: &[String],
+) -> Result<Vec<PathBuf>, AgentError> {
+    let Some(target) = secret_exclusions_file(project_root, agent) else {
+        return Ok(Vec::new());
+    };
+    if !target.exists() {
+        return Ok(Vec::new());
+    }
+    let raw = fs::read_to_string(&target).map_err(AgentError::Io)?;
+
+    match agent {
+        Agent::Claude => {
+            let Ok(serde_json::Value::Object(mut root)) = serde_json::from_str::<serde_json::Value>(&raw)
+            else {
+                return Ok(Vec::new());
+            };
+            let record = project_root.join(CLAUDE_DENY_RECORD);
+            let ours = if record.exists() {
+                let ours = read_record(&record);
+                fs::remove_file(&record).map_err(AgentError::Io)?;
+                ours
+            } else {
+                paths.iter().map(|p| claude_deny_rule(p)).collect()
+            };
+            let mut changed = false;
+            if let Some(serde_json::Value::Object(perms)) = root.get_mut("permissions") {
+                if let Some(serde_json::Value::Array(deny)) = perms.get_mut("deny") {
+                    let before = deny.len();
+                    deny.retain(|v| !v.as_str().is_some_and(|s| ours.iter().any(|o| o == s)));
+                    changed = deny.len() != before;
+                    if deny.is_empty() {
+                        perms.remove("deny");
+                    }
+                }
+                if perms.is_empty() {
+                    root.remove("permissions");
+                }
+            }
+            if !changed {
+                return Ok(Vec::new());
+            }
+            let pretty = serde_json::to_string_pretty(&serde_json::Value::Object(root))
+                .map_err(|e| AgentError::Io(std::io::Error::other(e)))?;
+            fs::write(&target, pretty).map_err(AgentError::Io)?;
+        }
+        _ => {
+            if !raw.contains(IGNORE_BLOCK_START) {
+                return Ok(Vec::new());
+            }
+            let remaining = strip_ignore_block(&raw);
+            if remaining.trim().is_empty() {
+                fs::remove_file(&target).map_err(AgentError::Io)?;
+            } else {
+                fs::write(&target, remaining).map_err(AgentError::Io)?;
+            }
+        }
+    }
+
+    Ok(vec![target])
+}
+
+/// Deny rules promptctl added to `.claude/settings.json`, one per line.
+/// settings.json entries cannot carry a marker, so `clean` and re-runs of
+/// `init` use this record to tell promptctl's rules from the user's.
+const CLAUDE_DENY_RECORD: &str = ".claude/promptctl-deny-rules.txt";
+
+fn claude_deny_rule(path: &str) -> String {
+    format!("Read(./{path})")
+}
+
+/// Entries of a promptctl record file, one per line, skipping `#` comments.
+fn read_record(record: &Path) -> Vec<String> {
+    fs::read_to_string(record)
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn write_deny_record(record: &Path, paths: &[String]) -> Result<(), AgentError> {
+    if paths.is_empty() {
+        if record.exists() {
+            fs::remove_file(record).map_err(AgentError::Io)?;
+        }
+        return Ok(());
+    }
+    let mut content =
+        String::from("# promptctl — deny rules it added to settings.json; used by `promptctl clean`\n");
+    for p in paths {
+        content.push_str(&claude_deny_rule(p));
+        content.push('\n');
+    }
+    fs::write(record, content).map_err(AgentError::Io)
+}
+
+/// Replace the `previous` deny rules promptctl added with `Read(./<path>)`
+/// rules for `paths`, keeping the user's rules.
+fn merge_claude_deny_rules(
+    settings_path: &Path,
+    previous: &[String],
+    paths: &[String],
+) -> Result<String, AgentError> {
+    let existing: serde_json::Value = if settings_path.exists() {
+        let raw = fs::read_to_string(settings_path).map_err(AgentError::Io)?;
+        serde_json::from_str(&raw).unwrap_or(serde_json::Value::Object(Default::default()))
+    } else {
+        serde_json::Value::Object(Default::default())
+    };
+
+    let mut root = match existing {
+        serde_json::Value::Object(m) => m,
+        _ => serde_json::Map::new(),
+    };
+
+    let perms = root
+        .entry("permissions")
+        .or_insert_with(|| serde_json::Value::Object(Default::default()));
+    if !perms.is_object() {
+        *perms = serde_json::Value::Object(Default::default());
+    }
+    let deny = perms
+        .as_object_mut()
+        .expect("permissions is an object")
+        .entry("deny")
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+    if !deny.is_array() {
+        *deny = serde_json::Value::Array(Vec::new());
+    }
+    let deny = deny.as_array_mut().expect("deny is an array");
+
+    deny.retain(|v| !v.as_str().is_some_and(|s| previous.iter().any(|p| p == s)));
+    for p in paths {
+        let rule = serde_json::Value::String(claude_deny_rule(p));
+        if !deny.contains(&rule) {
+            deny.push(rule);
+        }
+    }
+    if deny.is_empty() {
+        let perms = root
+            .get_mut("permissions")
+            .and_then(serde_json::Value::as_object_mut)
+            .expect("permissions is an object");
+        perms.remove("deny");
+        if perms.is_empty() {
+            root.remove("permissions");
+        }
+    }
+
+    serde_json::to_string_pretty(&serde_json::Value::Object(root))
+        .map_err(|e| AgentError::Io(std::io::Error::other(e)))
+}
+
+/// Remove the promptctl-managed block from an ignore file's content.
+fn strip_ignore_block(content: &str) -> String {
+    let mut out = String::new();
+    let mut inside = false;
+    for line in content.lines() {
+        if line.trim() == IGNORE_BLOCK_START {
+            inside = true;
+            continue;
+        }
+        if line.trim() == IGNORE_BLOCK_END {
+            inside = false;
+            continue;
+        }
+        if !inside {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    let trimmed = out.trim_end();
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("{trimmed}\n")
+    }
+}
+
 // ── Shared helpers ───────────────────────────────────────────────────────────
 
+/// Quote a word for bash unless it only holds characters that are safe bare.
+fn shell_quote(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_+,.=/:@%".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// A double-quoted YAML scalar. JSON string escaping is valid YAML, so quotes,
+/// backslashes and newlines in user text cannot break the frontmatter.
+fn yaml_quote(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+/// `init` flags as shell words, each quoted as needed.
+fn shell_args(args: &str) -> String {
+    args.split_whitespace().map(shell_quote).collect::<Vec<_>>().join(" ")
+}
+
 fn write_hook_file(path: &Path, content: &str, force: bool) -> Result<(), AgentError> {
     if path.exists() && !force {
         return Err(AgentError::AlreadyExists(path.to_path_buf()));
@@ -853,3 +2926,79 @@ fn make_executable(path: &Path) -> Result<(), AgentError> {
 fn make_executable(_path: &Path) -> Result<(), AgentError> {
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_start_script_passes_composite_role_to_show() {
+        let languages = vec!["go".to_string(), "typescript".to_string()];
+        let lang_args = std::collections::HashMap::from([(
+            "typescript".to_string(),
+            "--role senior+security --size compact --exclude-section patterns".to_string(),
+        )]);
+        let script = claude_session_start_script(&languages, "--role senior+security --size compact", &lang_args);
+        assert!(script.contains("promptctl show go --role senior+security --size compact 2>"));
+        assert!(script.contains(
+            "promptctl show typescript --role senior+security --size compact --exclude-section patterns 2>"
+        ));
+        assert!(!script.contains("show rust"));
+    }
+
+    /// Whether the protect guard's `case` statement for `glob` matches `path`.
+    fn guard_matches(glob: &str, path: &str) -> bool {
+        let patterns = glob_to_case_patterns(glob).join("|");
+        let script = format!("case \"$1\" in\n  {patterns}) exit 0 ;;\nesac\nexit 1");
+        std::process::Command::new("bash")
+            .args(["-c", &script, "guard", path])
+            .status()
+            .expect("bash runs")
+            .success()
+    }
+
+    #[test]
+    fn case_patterns_quote_shell_metacharacters() {
+        assert_eq!(glob_to_case_patterns("$HOME/*.rs"), ["'$HOME/'*.rs"]);
+        assert!(guard_matches("$HOME/*.rs", "$HOME/main.rs"));
+        assert!(!guard_matches("$HOME/*.rs", "root/main.rs"));
+
+        assert_eq!(glob_to_case_patterns("`id`.txt"), ["*/'`id`.txt'", "'`id`.txt'"]);
+        assert!(guard_matches("`id`.txt", "docs/`id`.txt"));
+        assert!(!guard_matches("`id`.txt", "uid=0(root).txt"));
+
+        assert!(guard_matches("it's.md", "it's.md"));
+        assert!(!guard_matches("it's.md", "its.md"));
+
+        assert!(guard_matches("my docs/*.md", "my docs/a.md"));
+        assert!(!guard_matches("my docs/*.md", "my/a.md"));
+        // A pattern cannot close the `case` arm early
+        assert!(!guard_matches("x) exit 0 ;; *", "anything"));
+    }
+
+    #[test]
+    fn case_patterns_follow_glob_semantics() {
+        // `**/` matches any depth, including none
+        assert!(guard_matches("**/gen/*.go", "gen/a.go"));
+        assert!(guard_matches("**/gen/*.go", "a/b/gen/a.go"));
+        assert!(!guard_matches("**/gen/*.go", "a/gen.go"));
+        assert!(guard_matches("vendor/**", "vendor/x/y.go"));
+        assert!(!guard_matches("vendor/**", "src/vendor.go"));
+
+        // Patterns without a slash match the file name at any depth
+        assert!(guard_matches("*.lock", "Cargo.lock"));
+        assert!(guard_matches("*.lock", "web/yarn.lock"));
+
+        assert_eq!(glob_to_case_patterns("src/*.{ts,tsx}"), ["src/*.ts", "src/*.tsx"]);
+        assert!(guard_matches("src/*.{ts,tsx}", "src/a.tsx"));
+        assert!(!guard_matches("src/*.{ts,tsx}", "src/a.js"));
+        assert!(!guard_matches("src/*.{ts,tsx}", "src/a.{ts,tsx}"));
+
+        assert!(guard_matches("gen/[ab].rs", "gen/a.rs"));
+        assert!(!guard_matches("gen/[ab].rs", "gen/c.rs"));
+        assert!(guard_matches("gen/[!ab].rs", "gen/c.rs"));
+        assert!(!guard_matches("gen/[!ab].rs", "gen/a.rs"));
+        // An unclosed bracket is literal
+        assert!(guard_matches("gen/[a.rs", "gen/[a.rs"));
+    }
+}
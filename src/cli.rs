@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(name = "promptctl")]
@@ -13,31 +13,50 @@ pub enum Commands {
     Init {
         agent: String,
 
-        #[arg(short, long, default_value = "developer")]
-        role: String,
+        #[command(flatten)]
+        options: InitOptions,
 
-        #[arg(short, long)]
-        path: Option<String>,
+        #[command(flatten)]
+        selection: SkillsetArgs,
+    },
 
+    Show {
+        language: String,
+
+        /// Role persona (developer, senior, reviewer, security, performance, documentation, mentor, devops); combine with `+`
         #[arg(short, long)]
-        force: bool,
+        role: Option<String>,
 
+        /// Explain which sections smart filtering keeps or drops, and why
         #[arg(long)]
-        dry_run: bool,
+        explain: bool,
 
-        #[arg(long)]
-        global: bool,
+        #[command(flatten)]
+        selection: SkillsetArgs,
     },
 
-    Show {
-        language: String,
+    List,
+
+    /// Check dependency manifests against the [dependencies] policy
+    CheckDeps {
+        /// Manifests to check (default: Cargo.toml, package.json, go.mod in the current directory)
+        files: Vec<String>,
 
-        /// Role persona (developer, senior, reviewer, security, performance, documentation, mentor, devops)
+        /// Only check dependencies missing from this copy of the manifest, e.g. its content before an edit
+        #[arg(long)]
+        base: Option<String>,
+    },
+
+    /// Report the tokens each installed agent file costs, and when it loads
+    Stats {
         #[arg(short, long)]
-        role: Option<String>,
+        path: Option<String>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
     },
 
-    List,
     Clean {
         agent: String,
 
@@ -45,3 +64,62 @@ pub enum Commands {
         path: Option<String>,
     },
 }
+
+/// Size tier and section selection for language skillsets
+#[derive(Args, Debug, Clone, Default)]
+pub struct SkillsetArgs {
+    /// Size tier: minimal, compact or full (default: `size` in .promptctl.toml, else full)
+    #[arg(long)]
+    pub size: Option<String>,
+
+    /// Only include these sections, e.g. --section style,error-handling
+    #[arg(long = "section", value_delimiter = ',')]
+    pub sections: Vec<String>,
+
+    /// Leave out these sections, e.g. --exclude-section async,patterns
+    #[arg(long = "exclude-section", value_delimiter = ',')]
+    pub exclude_sections: Vec<String>,
+}
+
+/// Flags that decide what `init` writes besides the base file
+#[derive(Args, Debug, Clone, Default)]
+pub struct InitOptions {
+    /// Role persona; combine with `+`, e.g. senior+security (default: the
+    /// role recorded in the existing file, else developer)
+    #[arg(short, long)]
+    pub role: Option<String>,
+
+    #[arg(short, long)]
+    pub path: Option<String>,
+
+    #[arg(short, long)]
+    pub force: bool,
+
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[arg(long)]
+    pub global: bool,
+
+    /// Fail instead of shrinking files that exceed the agent's token budget
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Also generate a Claude Code subagent for each role (claude only)
+    #[arg(long)]
+    pub subagents: bool,
+
+    /// Deliver language skillsets as Claude Code skills loaded on demand
+    /// instead of at session start (claude only)
+    #[arg(long)]
+    pub skills: bool,
+
+    /// Also generate a slash command per role (claude, copilot, cursor)
+    #[arg(long)]
+    pub commands: bool,
+
+    /// Also point VS Code's Copilot commit message and review instructions at
+    /// promptctl files in .vscode/settings.json (copilot only)
+    #[arg(long)]
+    pub vscode_settings: bool,
+}
@@ -1,5 +1,9 @@
 //! Configuration management for promptctl.
 
+use crate::indexer::is_detected_language;
+use crate::prompt_builder::{PromptSize, Section, StructuredPrompt};
+use crate::prompts::SkillsetFile;
+use crate::roles::Persona;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -7,6 +11,8 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 const CONFIG_FILENAME: &str = ".promptctl.toml";
+/// Directory next to the config file whose Markdown files extend skillsets
+const DOCS_DIRNAME: &str = ".promptctl";
 
 #[derive(Error, Debug)]
 pub enum ConfigError {
@@ -18,6 +24,30 @@ pub enum ConfigError {
     Serialize(#[from] toml::ser::Error),
     #[error("config file already exists at {0}")]
     AlreadyExists(PathBuf),
+    #[error("failed to read {path}: {source}")]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("rule '{0}' needs either `content` or `file`")]
+    EmptyRule(String),
+    #[error("rule title '{0}' needs at least one letter or digit to name its file")]
+    UntitledRule(String),
+    #[error("rules '{first}' and '{second}' would share the file name '{slug}' — rename one")]
+    DuplicateRule { first: String, second: String, slug: String },
+    #[error("unknown section '{section}' in [prompts.{language}.sections]")]
+    UnknownSection { language: String, section: String },
+    #[error("invalid skillset {path}: {source}")]
+    Skillset {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("section override '{section}' for '{language}' needs either `content` or `file`")]
+    EmptySection { language: String, section: String },
+    #[error("role '{0}' needs `prefix`, `prefix_file` or `combine`")]
+    EmptyRole(String),
+    #[error("[[roles.scope]] for '{glob}': {message}")]
+    RoleScope { glob: String, message: String },
 }
 
 /// How a custom prompt interacts with the built-in prompt for the same language
@@ -31,8 +61,38 @@ pub enum PromptMode {
     Merge,
 }
 
+/// What a section override does to the matching built-in section
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SectionAction {
+    /// Swap the section's content (created if the built-in lacks it)
+    #[default]
+    Replace,
+    /// Add content after the built-in section
+    Append,
+    /// Drop the section entirely
+    Disable,
+}
+
+/// Override for one section of a structured skillset, e.g.
+/// `[prompts.rust.sections.error-handling]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SectionOverride {
+    #[serde(default)]
+    pub action: SectionAction,
+    /// Heading to use instead of the built-in one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Markdown file relative to the config file, used when `content` is unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomPrompt {
+    #[serde(default)]
     pub name: String,
     #[serde(default)]
     pub content: String,
@@ -44,6 +104,183 @@ pub struct CustomPrompt {
     pub prepend: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub append: Option<String>,
+    /// File whose text replaces `content`, relative to the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_file: Option<PathBuf>,
+    /// File whose text replaces `prepend`, relative to the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prepend_file: Option<PathBuf>,
+    /// File whose text replaces `append`, relative to the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub append_file: Option<PathBuf>,
+    /// Per-section overrides for structured skillsets, keyed by section name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sections: HashMap<String, SectionOverride>,
+    /// File extensions (without the dot) that belong to this language
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<String>,
+    /// Globs for per-language hooks; derived from `extensions` when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub globs: Vec<String>,
+    /// Language-specific hallucination guardrails appended to the skillset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardrails: Option<String>,
+    /// Default size tier for `show` and hooks (minimal, compact, full)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<PromptSize>,
+}
+
+impl CustomPrompt {
+    /// True when the entry leaves the built-in text alone and only tunes it
+    /// (section overrides, size, guardrails): replace mode with no content.
+    pub fn keeps_builtin(&self) -> bool {
+        self.mode == PromptMode::Replace && self.content.is_empty()
+    }
+
+    /// Extensions normalized to lowercase without a leading dot.
+    pub fn normalized_extensions(&self) -> Vec<String> {
+        self.extensions
+            .iter()
+            .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect()
+    }
+
+    /// Globs for hook files: explicit `globs`, else one `**/*.<ext>` per extension.
+    pub fn file_globs(&self) -> Vec<String> {
+        if !self.globs.is_empty() {
+            return self.globs.clone();
+        }
+        self.normalized_extensions()
+            .iter()
+            .map(|e| format!("**/*.{e}"))
+            .collect()
+    }
+
+    /// Section overrides resolved to built-in sections, in a stable order.
+    pub fn section_overrides(&self) -> Vec<(Section, &SectionOverride)> {
+        let mut overrides: Vec<_> = self
+            .sections
+            .iter()
+            .filter_map(|(name, o)| Section::from_str(name).map(|s| (s, o)))
+            .collect();
+        overrides.sort_by_key(|(s, _)| Section::all().iter().position(|a| a == s));
+        overrides
+    }
+}
+
+/// Paths agents must never edit by hand (generated code, build outputs, migrations)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtectConfig {
+    /// Glob patterns relative to the project root, e.g. `src/generated/**`
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Why the paths are protected; shown to the agent when an edit is denied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Per-language dependency policy: which packages the team has approved
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyPolicy {
+    /// Approved packages. When non-empty, anything else is flagged as unlisted.
+    /// A trailing `*` matches by prefix (e.g. `github.com/acme/*`).
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// Packages that must not be added
+    #[serde(default)]
+    pub denied: Vec<String>,
+    /// Preferred alternatives: package → what to use instead
+    #[serde(default)]
+    pub preferred: HashMap<String, String>,
+}
+
+impl DependencyPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty() && self.preferred.is_empty()
+    }
+}
+
+/// A project-defined persona (`[roles.<name>]`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomRole {
+    #[serde(default)]
+    pub description: String,
+    /// Persona text placed before the base file and `show --role` output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// Markdown file with the persona text, relative to the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix_file: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Roles this one is made of, e.g. `["senior", "security"]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub combine: Vec<String>,
+    /// Skillset sections kept whatever the size tier or smart filter says
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<Section>,
+}
+
+/// `[roles]`: project personas by name, plus `[[roles.scope]]` entries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RolesConfig {
+    /// Roles applied to files matching a glob instead of the project-wide role
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<RoleScope>,
+    #[serde(flatten)]
+    pub custom: HashMap<String, CustomRole>,
+}
+
+/// A role for files matching a glob (`[[roles.scope]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleScope {
+    /// Glob relative to the project root, e.g. `contracts/**`
+    pub glob: String,
+    /// Any `--role` value, including custom and composite roles
+    pub role: String,
+}
+
+/// A custom rule scoped to files matching a glob (`[[rules]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopedRule {
+    /// Glob relative to the project root, e.g. `migrations/**`
+    pub glob: String,
+    pub title: String,
+    /// Inline rule text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Markdown file with the rule text, relative to the config file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+/// A scoped rule with its content loaded, ready to render for an agent
+#[derive(Debug, Clone)]
+pub struct ResolvedRule {
+    pub glob: String,
+    pub title: String,
+    pub content: String,
+}
+
+impl ResolvedRule {
+    /// File-name-safe identifier derived from the title
+    pub fn slug(&self) -> String {
+        slugify(&self.title)
+    }
+}
+
+/// Lowercase ASCII letters and digits joined by single hyphens.
+pub fn slugify(text: &str) -> String {
+    let slug: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -52,6 +289,30 @@ pub struct Config {
     pub prompts: HashMap<String, CustomPrompt>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_agent: Option<String>,
+    #[serde(default)]
+    pub protect: ProtectConfig,
+    #[serde(default)]
+    pub dependencies: HashMap<String, DependencyPolicy>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ScopedRule>,
+    /// Per-agent token budgets that override the built-in limits
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub budgets: HashMap<String, usize>,
+    /// Project personas, usable wherever `--role` is, and per-path roles
+    #[serde(default)]
+    pub roles: RolesConfig,
+    /// Directory holding the loaded config file; file references resolve against it
+    #[serde(skip)]
+    pub base_dir: PathBuf,
+    /// Markdown picked up from `.promptctl/<lang>.md` and `.promptctl/<lang>/*.md`
+    #[serde(skip)]
+    pub docs: HashMap<String, String>,
+    /// Project skillsets from `.promptctl/<lang>.toml`, keyed by language and aliases
+    #[serde(skip)]
+    pub skillsets: HashMap<String, StructuredPrompt>,
+    /// `.promptctl/` Markdown files and directories not named after a known language
+    #[serde(skip)]
+    pub ignored_docs: Vec<PathBuf>,
 }
 
 impl Config {
@@ -63,13 +324,145 @@ impl Config {
     pub fn load() -> Result<Option<Self>, ConfigError> {
         if let Some(path) = Self::find_config_file() {
             let content = fs::read_to_string(&path)?;
-            let config: Config = toml::from_str(&content)?;
+            let mut config: Config = toml::from_str(&content)?;
+            config.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            config.resolve_prompt_files()?;
+            config.load_docs()?;
             Ok(Some(config))
         } else {
             Ok(None)
         }
     }
 
+    /// Replace inline `content`/`prepend`/`append`/`prefix` with the text of their
+    /// `*_file` counterparts, so the rest of the pipeline only deals with strings.
+    fn resolve_prompt_files(&mut self) -> Result<(), ConfigError> {
+        let base_dir = self.base_dir.clone();
+        let read = |file: &Path| {
+            let path = base_dir.join(file);
+            fs::read_to_string(&path).map_err(|source| ConfigError::ReadFile { path, source })
+        };
+
+        for (language, prompt) in self.prompts.iter_mut() {
+            for (section, o) in prompt.sections.iter_mut() {
+                if Section::from_str(section).is_none() {
+                    return Err(ConfigError::UnknownSection {
+                        language: language.clone(),
+                        section: section.clone(),
+                    });
+                }
+                if o.content.is_none()
+                    && let Some(file) = &o.file
+                {
+                    o.content = Some(read(file)?);
+                }
+                if o.action != SectionAction::Disable && o.content.is_none() {
+                    return Err(ConfigError::EmptySection {
+                        language: language.clone(),
+                        section: section.clone(),
+                    });
+                }
+            }
+            if let Some(file) = &prompt.content_file {
+                prompt.content = read(file)?;
+            }
+            if let Some(file) = &prompt.prepend_file {
+                prompt.prepend = Some(read(file)?);
+            }
+            if let Some(file) = &prompt.append_file {
+                prompt.append = Some(read(file)?);
+            }
+        }
+
+        for (name, role) in self.roles.custom.iter_mut() {
+            if role.prefix.is_none()
+                && let Some(file) = &role.prefix_file
+            {
+                role.prefix = Some(read(file)?);
+            }
+            if role.prefix.is_none() && role.combine.is_empty() {
+                return Err(ConfigError::EmptyRole(name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Collect `.promptctl/<lang>.md` and `.promptctl/<lang>/*.md` per language,
+    /// and parse `.promptctl/<lang>.toml` skillsets.
+    fn load_docs(&mut self) -> Result<(), ConfigError> {
+        let dir = self.base_dir.join(DOCS_DIRNAME);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(());
+        };
+
+        // (language, nested under `<lang>/`, path)
+        let mut files: Vec<(String, bool, PathBuf)> = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()) else {
+                continue;
+            };
+            if path.is_dir() {
+                let Ok(inner) = fs::read_dir(&path) else {
+                    continue;
+                };
+                for file in inner.filter_map(Result::ok).map(|e| e.path()) {
+                    if is_markdown(&file) {
+                        files.push((stem.clone(), true, file));
+                    }
+                }
+            } else if is_markdown(&path) && stem != "readme" {
+                files.push((stem, false, path));
+            } else if path.extension().is_some_and(|e| e == "toml") {
+                let text = fs::read_to_string(&path).map_err(|source| ConfigError::ReadFile {
+                    path: path.clone(),
+                    source,
+                })?;
+                let skillset = SkillsetFile::parse(&text)
+                    .map_err(|source| ConfigError::Skillset { path, source })?;
+                let names = skillset.names();
+                let prompt = skillset.into_prompt();
+                for name in names {
+                    self.skillsets.insert(name, prompt.clone());
+                }
+            }
+        }
+        // `<lang>.md` comes before `<lang>/…`, then files within a directory by name
+        files.sort();
+
+        for (lang, nested, path) in files {
+            if !self.is_known_language(&lang) {
+                let ignored = match path.parent() {
+                    Some(parent) if nested => parent.to_path_buf(),
+                    _ => path,
+                };
+                if !self.ignored_docs.contains(&ignored) {
+                    self.ignored_docs.push(ignored);
+                }
+                continue;
+            }
+            let text = fs::read_to_string(&path).map_err(|source| ConfigError::ReadFile {
+                path: path.clone(),
+                source,
+            })?;
+            let entry = self.docs.entry(lang).or_default();
+            if !entry.is_empty() {
+                entry.push_str("\n\n");
+            }
+            entry.push_str(text.trim());
+        }
+        Ok(())
+    }
+
+    /// A language with a built-in or project skillset, a `[prompts.<lang>]`
+    /// entry, or files the scan detects.
+    fn is_known_language(&self, language: &str) -> bool {
+        crate::prompts::get_structured_prompt(language).is_some()
+            || self.skillsets.contains_key(language)
+            || self.prompts.keys().any(|l| l.eq_ignore_ascii_case(language))
+            || is_detected_language(language)
+    }
+
     fn find_config_file() -> Option<PathBuf> {
         let mut current = std::env::current_dir().ok()?;
 
@@ -113,7 +506,44 @@ impl Config {
 # Re-run 'promptctl init <agent>' after editing.
 
 # Optional: set a default AI agent for this project
-# default_agent = "copilot"  # copilot, claude, cursor, codex, aider
+# default_agent = "copilot"  # copilot, claude, cursor, windsurf, cline, roo, gemini, codex, aider
+
+# ── Token budgets ──
+# Emitted files are checked against each agent's budget; skillsets over it
+# lose their least relevant sections first (`init --strict` fails instead).
+# [budgets]
+# copilot = 3000
+# cursor = 2500
+
+# ── Protected paths ──
+# Files agents must never edit by hand. Listed in every agent's base file and
+# enforced for Claude Code by a PreToolUse hook that denies Write/Edit calls.
+# [protect]
+# paths = ["src/generated/**", "migrations/**", "build/**", "abi/*.json"]
+# reason = "Generated files — change the generator or source instead."
+
+# ── Dependency policy ──
+# Approved, denied and preferred packages per language. Rendered into each
+# skillset's Dependencies section; Claude Code also gets a hook that flags
+# manifest edits (Cargo.toml, package.json, go.mod) that break the policy.
+# [dependencies.rust]
+# allowed = ["serde", "tokio", "tracing", "thiserror"]
+# denied = ["log", "openssl"]
+# preferred = { log = "tracing", openssl = "rustls" }
+
+# ── Path-scoped rules ──
+# Rules that apply only to matching files. Copilot, Cursor and Windsurf get
+# scoped rule files, Claude Code gets them injected before edits to matching
+# paths, and Codex gets a nested AGENTS.md when the glob starts with a directory.
+# [[rules]]
+# glob = "migrations/**"
+# title = "Migrations"
+# content = "Never rewrite migrations that have already been applied."
+#
+# [[rules]]
+# glob = "src/api/**"
+# title = "API handlers"
+# file = "docs/rules/api.md"   # relative to this file
 
 # ── Prompt modes ──
 # mode = "replace"  → fully replace the built-in prompt (default)
@@ -121,6 +551,11 @@ impl Config {
 # mode = "prepend"  → add your content BEFORE the built-in prompt
 # mode = "merge"    → use both prepend and append around the built-in
 
+# Long rules can live in Markdown files instead of TOML strings:
+# content_file / prepend_file / append_file are resolved relative to this file.
+# Markdown in .promptctl/<lang>.md or .promptctl/<lang>/*.md next to this file
+# is appended to that language's skillset automatically.
+
 # Example: extend built-in Rust prompt with project-specific rules
 # [prompts.rust]
 # name = "Rust"
@@ -130,6 +565,52 @@ impl Config {
 # - Use workspace dependencies from root Cargo.toml
 # - All public APIs must have doc comments
 # """
+
+# Example: a language promptctl has no skillset for. `extensions` feed
+# detection and hooks; `globs` default to **/*.<ext>.
+# [prompts.elixir]
+# name = "Elixir"
+# extensions = ["ex", "exs"]
+# guardrails = "- Do not invent OTP behaviours or callbacks"
+# content = """
+# - Prefer pattern matching in function heads over conditionals
+# """
+
+# Example: ship compact skillsets to agents with small context windows
+# [prompts.typescript]
+# size = "compact"   # minimal | compact | full (default)
+
+# Example: a project persona, usable with --role auditor-zk (or --role zk)
+# [roles.auditor-zk]
+# description = "Zero-knowledge circuit auditing"
+# aliases = ["zk"]
+# sections = ["security", "testing"]   # kept whatever the size tier
+# prefix = """
+# ## Role: ZK Auditor
+# Check every constraint for under-constrained witnesses.
+# """
+# # or: prefix_file = "docs/roles/auditor-zk.md"
+#
+# Example: a composite role (also: --role senior+security)
+# [roles.lead]
+# combine = ["senior", "security"]
+
+# Example: roles for parts of the tree, delivered like path-scoped rules
+# [[roles.scope]]
+# glob = "contracts/**"
+# role = "security"
+#
+# [[roles.scope]]
+# glob = "docs/**"
+# role = "documentation"
+
+# Example: override single sections of a built-in skillset
+# (action = "replace" | "append" | "disable"; content or file)
+# [prompts.rust.sections.error-handling]
+# content = "- Use `snafu` for all error types"
+#
+# [prompts.rust.sections.async]
+# action = "disable"
 "#
         .to_string()
     }
@@ -141,7 +622,7 @@ impl Config {
     /// Resolve a prompt for a language, applying merge logic.
     /// Returns the final prompt string, merging custom + built-in as needed.
     pub fn resolve_prompt(&self, language: &str, builtin: Option<&str>) -> Option<String> {
-        let custom = self.prompts.get(language)?;
+        let custom = self.prompts.get(language).filter(|c| !c.keeps_builtin())?;
 
         match custom.mode {
             PromptMode::Replace => {
@@ -177,7 +658,155 @@ impl Config {
         }
     }
 
+    /// Token budget configured for an agent in `[budgets]`.
+    pub fn token_budget(&self, agent: &str) -> Option<usize> {
+        self.budgets.get(agent).copied()
+    }
+
+    /// Dependency policy for a language, if one is configured.
+    pub fn dependency_policy(&self, language: &str) -> Option<&DependencyPolicy> {
+        self.dependencies.get(language).filter(|p| !p.is_empty())
+    }
+
+    /// Read a file referenced from the config, relative to the config file.
+    pub fn read_relative(&self, file: &Path) -> Result<String, ConfigError> {
+        let path = self.base_dir.join(file);
+        fs::read_to_string(&path).map_err(|source| ConfigError::ReadFile { path, source })
+    }
+
+    /// Load every `[[rules]]` entry's content, inline or from its file, followed
+    /// by the `[[roles.scope]]` entries, which are delivered the same way.
+    pub fn resolved_rules(&self) -> Result<Vec<ResolvedRule>, ConfigError> {
+        let mut rules = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let content = match (&rule.content, &rule.file) {
+                    (Some(c), _) => c.clone(),
+                    (None, Some(f)) => self.read_relative(f)?,
+                    (None, None) => return Err(ConfigError::EmptyRule(rule.title.clone())),
+                };
+                Ok(ResolvedRule {
+                    glob: rule.glob.clone(),
+                    title: rule.title.clone(),
+                    content: content.trim().to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Each rule is written to a file named after its slug
+        for (i, rule) in rules.iter().enumerate() {
+            let slug = rule.slug();
+            if slug.is_empty() {
+                return Err(ConfigError::UntitledRule(rule.title.clone()));
+            }
+            if let Some(first) = rules[..i].iter().find(|r| r.slug() == slug) {
+                return Err(ConfigError::DuplicateRule {
+                    first: first.title.clone(),
+                    second: rule.title.clone(),
+                    slug,
+                });
+            }
+        }
+
+        for scope in &self.roles.scope {
+            let persona = Persona::resolve(&scope.role, Some(self)).map_err(|message| {
+                ConfigError::RoleScope {
+                    glob: scope.glob.clone(),
+                    message,
+                }
+            })?;
+            // The persona's own `## Role: …` heading becomes the rule title
+            let prefix = persona.prefix.trim();
+            let (heading, body) = prefix.split_once('\n').unwrap_or((prefix, ""));
+            let name = heading.trim_start_matches("## ").trim_start_matches("Role: ");
+            let mut title = format!("Role: {name}");
+            if rules.iter().any(|r| r.slug() == slugify(&title)) {
+                title = format!("{title} for {}", scope.glob);
+            }
+            let base = title.clone();
+            let mut n = 2;
+            while rules.iter().any(|r| r.slug() == slugify(&title)) {
+                title = format!("{base} ({n})");
+                n += 1;
+            }
+            rules.push(ResolvedRule {
+                glob: scope.glob.clone(),
+                title,
+                content: format!(
+                    "For these files, take this role instead of the project-wide one.\n\n{}",
+                    body.trim()
+                ),
+            });
+        }
+        Ok(rules)
+    }
+
+    /// Extension → language map declared by `[prompts.<lang>] extensions`.
+    pub fn custom_extensions(&self) -> HashMap<String, String> {
+        let mut map = HashMap::new();
+        for (lang, prompt) in &self.prompts {
+            for ext in prompt.normalized_extensions() {
+                map.insert(ext, lang.clone());
+            }
+        }
+        map
+    }
+
+    /// Project role by name or alias, with its canonical name.
+    pub fn custom_role(&self, name: &str) -> Option<(&str, &CustomRole)> {
+        let name = name.to_lowercase();
+        self.roles
+            .custom
+            .iter()
+            .find(|(key, role)| {
+                key.to_lowercase() == name || role.aliases.iter().any(|a| a.to_lowercase() == name)
+            })
+            .map(|(key, role)| (key.as_str(), role))
+    }
+
+    /// Names of project roles, sorted.
+    pub fn custom_role_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.roles.custom.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+
+    /// Project skillset from `.promptctl/<lang>.toml`; takes the place of a built-in.
+    pub fn skillset(&self, language: &str) -> Option<&StructuredPrompt> {
+        self.skillsets.get(language)
+    }
+
+    /// Languages with a project skillset (primary names only, no aliases).
+    pub fn skillset_languages(&self) -> Vec<&str> {
+        let mut langs: Vec<&str> = self.skillsets.values().map(|s| s.language.as_str()).collect();
+        langs.sort();
+        langs.dedup();
+        langs
+    }
+
+    /// Team docs from the `.promptctl/` directory for a language.
+    pub fn language_docs(&self, language: &str) -> Option<&str> {
+        self.docs.get(language).map(String::as_str)
+    }
+
     pub fn custom_languages(&self) -> Vec<&str> {
         self.prompts.keys().map(String::as_str).collect()
     }
+
+    /// Languages that only have `.promptctl/` docs and no `[prompts.<lang>]` entry.
+    pub fn docs_only_languages(&self) -> Vec<&str> {
+        let mut langs: Vec<&str> = self
+            .docs
+            .keys()
+            .filter(|l| !self.prompts.contains_key(*l) && !self.skillsets.contains_key(*l))
+            .map(String::as_str)
+            .collect();
+        langs.sort();
+        langs
+    }
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|e| e == "md")
 }
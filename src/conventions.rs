@@ -0,0 +1,746 @@
+//! Code-convention extraction from formatter and linter configs.
+//!
+//! Reads the project's own style configuration (rustfmt, clippy, EditorConfig,
+//! ESLint, Prettier, Biome, golangci-lint, Ruff/Black, Solhint, forge fmt) and
+//! turns it into concrete rules that are rendered into the matching language
+//! skillset. The agent then follows the project's actual style instead of the
+//! generic advice in the built-in prompts.
+
+use crate::indexer::language_for_extension;
+use std::fs;
+use std::path::Path;
+
+/// A single style rule extracted from a project config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Convention {
+    /// Languages the rule applies to (lowercased). Empty means every language.
+    pub languages: Vec<String>,
+    /// Where the rule was read from, relative to the project root.
+    pub source: String,
+    /// Human-readable rule, e.g. `max_width = 120` or "Indent with tabs".
+    pub rule: String,
+    /// Config key the rule sets, for sources where a later section overrides
+    /// an earlier one, e.g. `indent_style` in `.editorconfig`.
+    pub setting: Option<String>,
+}
+
+impl Convention {
+    fn new(languages: &[&str], source: &str, rule: impl Into<String>) -> Self {
+        Self {
+            languages: languages.iter().map(|l| l.to_string()).collect(),
+            source: source.to_string(),
+            rule: rule.into(),
+            setting: None,
+        }
+    }
+
+    /// Whether this rule applies to the given (lowercased) language.
+    pub fn applies_to(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|l| l == language)
+    }
+}
+
+const JS_LANGS: &[&str] = &["typescript", "javascript"];
+
+/// Detect conventions from config files at the project root.
+pub fn detect(root: &Path) -> Vec<Convention> {
+    let mut out = Vec::new();
+
+    detect_rustfmt(root, &mut out);
+    detect_clippy(root, &mut out);
+    detect_editorconfig(root, &mut out);
+    detect_eslint(root, &mut out);
+    detect_prettier(root, &mut out);
+    detect_biome(root, &mut out);
+    detect_golangci(root, &mut out);
+    detect_python(root, &mut out);
+    detect_solidity(root, &mut out);
+
+    out.dedup();
+    out
+}
+
+/// Render the conventions that apply to `language` as a skillset section.
+/// Returns `None` when the project declares nothing relevant.
+pub fn render(conventions: &[Convention], language: &str) -> Option<String> {
+    let lang = language.to_lowercase();
+    let applicable: Vec<&Convention> = conventions.iter().filter(|c| c.applies_to(&lang)).collect();
+    // Of the rules that set the same key from the same source, the last wins
+    let relevant: Vec<&Convention> = applicable
+        .iter()
+        .enumerate()
+        .filter(|&(i, c)| {
+            c.setting.is_none()
+                || !applicable[i + 1..]
+                    .iter()
+                    .any(|later| later.source == c.source && later.setting == c.setting)
+        })
+        .map(|(_, c)| *c)
+        .collect();
+    if relevant.is_empty() {
+        return None;
+    }
+
+    let mut output = String::from("## Project Conventions\n\n");
+    output.push_str(
+        "These rules come from this project's formatter and linter configuration. \
+         They take precedence over the generic style guidance above.\n",
+    );
+
+    let mut current_source = "";
+    for c in relevant {
+        if c.source != current_source {
+            output.push_str(&format!("\n### {}\n\n", c.source));
+            current_source = &c.source;
+        }
+        output.push_str(&format!("- {}\n", c.rule));
+    }
+
+    Some(output.trim_end().to_string())
+}
+
+// ── Rust ─────────────────────────────────────────────────────────────────────
+
+fn detect_rustfmt(root: &Path, out: &mut Vec<Convention>) {
+    for name in ["rustfmt.toml", ".rustfmt.toml"] {
+        if let Some(table) = read_toml(&root.join(name)) {
+            for (key, value) in &table {
+                out.push(Convention::new(&["rust"], name, format!("`{key} = {value}`")));
+            }
+            break;
+        }
+    }
+}
+
+fn detect_clippy(root: &Path, out: &mut Vec<Convention>) {
+    for name in ["clippy.toml", ".clippy.toml"] {
+        if let Some(table) = read_toml(&root.join(name)) {
+            for (key, value) in &table {
+                out.push(Convention::new(&["rust"], name, format!("`{key} = {value}`")));
+            }
+            break;
+        }
+    }
+
+    // [lints] / [workspace.lints] tables in Cargo.toml
+    if let Some(cargo) = read_toml(&root.join("Cargo.toml")) {
+        let tables = [
+            ("lints", cargo.get("lints")),
+            (
+                "workspace.lints",
+                cargo.get("workspace").and_then(|w| w.get("lints")),
+            ),
+        ];
+        for (prefix, lints) in tables {
+            let Some(lints) = lints.and_then(toml::Value::as_table) else {
+                continue;
+            };
+            for (tool, entries) in lints {
+                let Some(entries) = entries.as_table() else {
+                    continue;
+                };
+                let source = format!("Cargo.toml [{prefix}.{tool}]");
+                for (lint, level) in entries {
+                    let level = level
+                        .as_str()
+                        .or_else(|| level.get("level").and_then(toml::Value::as_str))
+                        .unwrap_or("warn");
+                    out.push(Convention::new(&["rust"], &source, lint_rule(tool, lint, level)));
+                }
+            }
+        }
+    }
+
+    // Crate-level lint attributes, e.g. #![warn(clippy::pedantic)]
+    for entry in ["src/lib.rs", "src/main.rs"] {
+        let Ok(content) = fs::read_to_string(root.join(entry)) else {
+            continue;
+        };
+        for line in content.lines().map(str::trim) {
+            let is_lint_attr = ["#![warn(", "#![deny(", "#![forbid(", "#![allow("]
+                .iter()
+                .any(|p| line.starts_with(p));
+            if is_lint_attr {
+                out.push(Convention::new(&["rust"], entry, format!("`{line}`")));
+            }
+        }
+    }
+}
+
+const CLIPPY_GROUPS: &[&str] = &[
+    "all",
+    "correctness",
+    "suspicious",
+    "style",
+    "complexity",
+    "perf",
+    "pedantic",
+    "nursery",
+    "cargo",
+    "restriction",
+];
+
+fn lint_rule(tool: &str, lint: &str, level: &str) -> String {
+    if tool == "clippy" && CLIPPY_GROUPS.contains(&lint) {
+        if level == "allow" {
+            format!("Clippy `{lint}` lint group allowed")
+        } else {
+            format!("Clippy `{lint}` lint group enabled ({level})")
+        }
+    } else if tool == "rust" {
+        format!("`{lint}` = {level}")
+    } else {
+        format!("`{tool}::{lint}` = {level}")
+    }
+}
+
+// ── EditorConfig ─────────────────────────────────────────────────────────────
+
+fn detect_editorconfig(root: &Path, out: &mut Vec<Convention>) {
+    let Ok(content) = fs::read_to_string(root.join(".editorconfig")) else {
+        return;
+    };
+
+    let mut languages: Option<Vec<&str>> = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            languages = editorconfig_languages(glob);
+            continue;
+        }
+        // Properties before the first section (e.g. `root = true`) and sections for
+        // files we do not map to a language are not skillset rules.
+        let Some(langs) = &languages else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim().to_lowercase(), value.trim());
+        if key == "root" {
+            continue;
+        }
+        let rule = match (key.as_str(), value) {
+            ("indent_style", "tab") => "Indent with tabs".to_string(),
+            ("indent_style", "space") => "Indent with spaces".to_string(),
+            ("indent_size", n) if n != "tab" => format!("Indent size: {n}"),
+            ("max_line_length", n) if n != "off" => format!("Max line width: {n}"),
+            ("end_of_line", eol) => format!("Line endings: {}", eol.to_uppercase()),
+            ("insert_final_newline", "true") => "End files with a newline".to_string(),
+            ("trim_trailing_whitespace", "true") => "Trim trailing whitespace".to_string(),
+            _ => format!("`{key} = {value}`"),
+        };
+        // Sections later in the file override earlier ones for the files
+        // both match; `render` keeps only the last rule per key
+        out.push(Convention {
+            setting: Some(key),
+            ..Convention::new(langs, ".editorconfig", rule)
+        });
+    }
+}
+
+/// Map an EditorConfig section glob to the languages it covers. Globs that
+/// match every file (`*`, `**`) return an empty list, meaning "all languages";
+/// globs for files we do not map (e.g. `*.md`) return `None`.
+fn editorconfig_languages(glob: &str) -> Option<Vec<&'static str>> {
+    if matches!(glob, "*" | "**" | "**/*") {
+        return Some(Vec::new());
+    }
+
+    // Expand `*.{rs,go}` and `*.rs` forms into extensions.
+    let ext_part = glob.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+    let exts: Vec<&str> = ext_part
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .split(',')
+        .map(str::trim)
+        .collect();
+
+    let mut langs: Vec<&'static str> = exts.iter().filter_map(|e| language_for_extension(e)).collect();
+    langs.sort_unstable();
+    langs.dedup();
+    (!langs.is_empty()).then_some(langs)
+}
+
+// ── JavaScript / TypeScript ──────────────────────────────────────────────────
+
+fn detect_eslint(root: &Path, out: &mut Vec<Convention>) {
+    for name in [".eslintrc.json", ".eslintrc", ".eslintrc.yml", ".eslintrc.yaml"] {
+        let Ok(content) = fs::read_to_string(root.join(name)) else {
+            continue;
+        };
+        let Some(config) = parse_json_or_yaml(&content) else {
+            continue;
+        };
+
+        if let Some(extends) = config.get("extends") {
+            let list = match extends {
+                serde_json::Value::String(s) => vec![s.clone()],
+                serde_json::Value::Array(a) => a
+                    .iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            if !list.is_empty() {
+                out.push(Convention::new(JS_LANGS, name, format!("Extends: {}", list.join(", "))));
+            }
+        }
+        if let Some(serde_json::Value::Object(rules)) = config.get("rules") {
+            for (rule, setting) in rules {
+                out.push(Convention::new(JS_LANGS, name, format!("`{rule}`: {setting}")));
+            }
+        }
+        return;
+    }
+
+    // Flat config is JavaScript — we cannot evaluate it, so only point at it.
+    for name in [
+        "eslint.config.js",
+        "eslint.config.mjs",
+        "eslint.config.cjs",
+        "eslint.config.ts",
+        ".eslintrc.js",
+        ".eslintrc.cjs",
+    ] {
+        if root.join(name).exists() {
+            out.push(Convention::new(
+                JS_LANGS,
+                name,
+                format!("Code must pass ESLint with the rules in `{name}`"),
+            ));
+            return;
+        }
+    }
+}
+
+fn detect_prettier(root: &Path, out: &mut Vec<Convention>) {
+    let mut found = None;
+    for name in [".prettierrc", ".prettierrc.json", ".prettierrc.yml", ".prettierrc.yaml"] {
+        if let Ok(content) = fs::read_to_string(root.join(name))
+            && let Some(config) = parse_json_or_yaml(&content)
+        {
+            found = Some((name.to_string(), config));
+            break;
+        }
+    }
+    if found.is_none()
+        && let Some(pkg) = read_json(&root.join("package.json"))
+        && let Some(config) = pkg.get("prettier")
+        && config.is_object()
+    {
+        found = Some(("package.json (prettier)".to_string(), config.clone()));
+    }
+
+    let Some((source, serde_json::Value::Object(config))) = found else {
+        return;
+    };
+    for (key, value) in &config {
+        let rule = match (key.as_str(), value) {
+            ("useTabs", serde_json::Value::Bool(true)) => "Indent with tabs".to_string(),
+            ("useTabs", serde_json::Value::Bool(false)) => "Indent with spaces".to_string(),
+            ("singleQuote", serde_json::Value::Bool(true)) => "Use single quotes".to_string(),
+            ("singleQuote", serde_json::Value::Bool(false)) => "Use double quotes".to_string(),
+            ("semi", serde_json::Value::Bool(false)) => "Omit semicolons".to_string(),
+            ("semi", serde_json::Value::Bool(true)) => "Always use semicolons".to_string(),
+            ("tabWidth", n) => format!("Indent size: {n}"),
+            ("printWidth", n) => format!("Max line width: {n}"),
+            ("trailingComma", serde_json::Value::String(s)) => format!("Trailing commas: {s}"),
+            _ => format!("`{key}: {value}`"),
+        };
+        out.push(Convention::new(JS_LANGS, &source, rule));
+    }
+}
+
+fn detect_biome(root: &Path, out: &mut Vec<Convention>) {
+    let Some((name, config)) = ["biome.json", "biome.jsonc"]
+        .into_iter()
+        .find_map(|n| read_json(&root.join(n)).map(|c| (n, c)))
+    else {
+        return;
+    };
+
+    let formatter = &config["formatter"];
+    match formatter["indentStyle"].as_str() {
+        Some("tab") => out.push(Convention::new(JS_LANGS, name, "Indent with tabs")),
+        Some("space") => out.push(Convention::new(JS_LANGS, name, "Indent with spaces")),
+        _ => {}
+    }
+    if let Some(n) = formatter["indentWidth"].as_u64() {
+        out.push(Convention::new(JS_LANGS, name, format!("Indent size: {n}")));
+    }
+    if let Some(n) = formatter["lineWidth"].as_u64() {
+        out.push(Convention::new(JS_LANGS, name, format!("Max line width: {n}")));
+    }
+
+    let js = &config["javascript"]["formatter"];
+    match js["quoteStyle"].as_str() {
+        Some("single") => out.push(Convention::new(JS_LANGS, name, "Use single quotes")),
+        Some("double") => out.push(Convention::new(JS_LANGS, name, "Use double quotes")),
+        _ => {}
+    }
+    match js["semicolons"].as_str() {
+        Some("asNeeded") => out.push(Convention::new(JS_LANGS, name, "Omit semicolons")),
+        Some("always") => out.push(Convention::new(JS_LANGS, name, "Always use semicolons")),
+        _ => {}
+    }
+
+    if config["linter"]["rules"]["recommended"].as_bool() == Some(true) {
+        out.push(Convention::new(JS_LANGS, name, "Biome recommended lint rules enabled"));
+    }
+}
+
+// ── Go ───────────────────────────────────────────────────────────────────────
+
+fn detect_golangci(root: &Path, out: &mut Vec<Convention>) {
+    let Some((name, config)) = [".golangci.yml", ".golangci.yaml"]
+        .into_iter()
+        .find_map(|n| fs::read_to_string(root.join(n)).ok().map(|c| (n, c)))
+        .and_then(|(n, c)| parse_json_or_yaml(&c).map(|config| (n, config)))
+    else {
+        return;
+    };
+
+    // Only the top-level `enable`/`disable` lists select linters; the ones
+    // under `linters.settings.*` configure individual checks.
+    let mut on = Vec::new();
+    let mut off = Vec::new();
+    for section in ["linters", "formatters"] {
+        let table = &config[section];
+        if let Some(default) = table["default"].as_str() {
+            out.push(Convention::new(&["go"], name, format!("Default {section}: {default}")));
+        }
+        let names = |key: &str| -> Vec<String> {
+            table[key]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(serde_json::Value::as_str)
+                .map(|v| format!("`{v}`"))
+                .collect()
+        };
+        on.extend(names("enable"));
+        off.extend(names("disable"));
+    }
+    if !on.is_empty() {
+        out.push(Convention::new(&["go"], name, format!("Enabled: {}", on.join(", "))));
+    }
+    if !off.is_empty() {
+        out.push(Convention::new(&["go"], name, format!("Disabled: {}", off.join(", "))));
+    }
+}
+
+// ── Python ───────────────────────────────────────────────────────────────────
+
+fn detect_python(root: &Path, out: &mut Vec<Convention>) {
+    // Standalone ruff config takes precedence over pyproject.toml.
+    let ruff = ["ruff.toml", ".ruff.toml"]
+        .into_iter()
+        .find_map(|n| read_toml(&root.join(n)).map(|t| (n.to_string(), toml::Value::Table(t))));
+    let pyproject = read_toml(&root.join("pyproject.toml"));
+
+    let ruff = ruff.or_else(|| {
+        pyproject
+            .as_ref()
+            .and_then(|p| p.get("tool"))
+            .and_then(|t| t.get("ruff"))
+            .map(|r| ("pyproject.toml [tool.ruff]".to_string(), r.clone()))
+    });
+
+    if let Some((source, table)) = ruff {
+        push_toml_scalars(&table, &["python"], &source, out);
+        for sub in ["lint", "format"] {
+            if let Some(t) = table.get(sub) {
+                push_toml_scalars(t, &["python"], &source, out);
+            }
+        }
+    }
+
+    if let Some(black) = pyproject
+        .as_ref()
+        .and_then(|p| p.get("tool"))
+        .and_then(|t| t.get("black"))
+    {
+        push_toml_scalars(black, &["python"], "pyproject.toml [tool.black]", out);
+    }
+}
+
+// ── Solidity ─────────────────────────────────────────────────────────────────
+
+fn detect_solidity(root: &Path, out: &mut Vec<Convention>) {
+    if let Some(config) = read_json(&root.join(".solhint.json")) {
+        if let Some(extends) = config["extends"].as_str() {
+            out.push(Convention::new(&["solidity"], ".solhint.json", format!("Extends: {extends}")));
+        }
+        if let Some(rules) = config["rules"].as_object() {
+            for (rule, setting) in rules {
+                out.push(Convention::new(&["solidity"], ".solhint.json", format!("`{rule}`: {setting}")));
+            }
+        }
+    }
+
+    if let Some(fmt) = read_toml(&root.join("foundry.toml")).and_then(|t| t.get("fmt").cloned()) {
+        push_toml_scalars(&fmt, &["solidity"], "foundry.toml [fmt]", out);
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────────────
+
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    let content = fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_json_comments(&content)).ok()
+}
+
+/// Push every scalar or array key of a TOML table as `key = value`.
+fn push_toml_scalars(table: &toml::Value, languages: &[&str], source: &str, out: &mut Vec<Convention>) {
+    let Some(table) = table.as_table() else {
+        return;
+    };
+    for (key, value) in table {
+        if !value.is_table() {
+            out.push(Convention::new(languages, source, format!("`{key} = {value}`")));
+        }
+    }
+}
+
+/// Drop `//` line comments so JSONC files (biome.jsonc, .eslintrc) parse.
+fn strip_json_comments(content: &str) -> String {
+    content
+        .lines()
+        .filter(|l| !l.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a config that may be JSON or block-style YAML — nested maps and
+/// lists, as in an `.eslintrc.yml` `rules:` section, with scalar leaves.
+fn parse_json_or_yaml(content: &str) -> Option<serde_json::Value> {
+    let trimmed = content.trim_start();
+    if trimmed.starts_with('{') {
+        return serde_json::from_str(&strip_json_comments(content)).ok();
+    }
+
+    let lines: Vec<(usize, &str)> = content
+        .lines()
+        .map(|l| (l.len() - l.trim_start().len(), l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#') && *l != "---")
+        .collect();
+    let mut pos = 0;
+    let value = parse_yaml_block(&lines, &mut pos, 0)?;
+    value.as_object().is_some_and(|m| !m.is_empty()).then_some(value)
+}
+
+/// Parse the map or list whose lines start at `indent`, advancing `pos`.
+fn parse_yaml_block(lines: &[(usize, &str)], pos: &mut usize, indent: usize) -> Option<serde_json::Value> {
+    let &(_, first) = lines.get(*pos)?;
+
+    if first == "-" || first.starts_with("- ") {
+        let mut items = Vec::new();
+        while let Some(&(ind, line)) = lines.get(*pos) {
+            if ind != indent || !(line == "-" || line.starts_with("- ")) {
+                break;
+            }
+            *pos += 1;
+            let item = line[1..].trim();
+            match item.split_once(": ") {
+                // `- key: value` — a one-entry map, e.g. rule options
+                Some((key, value)) if !item.starts_with(['"', '\'']) => {
+                    let mut entry = serde_json::Map::new();
+                    entry.insert(key.trim().to_string(), parse_yaml_scalar(value));
+                    items.push(serde_json::Value::Object(entry));
+                }
+                _ => items.push(parse_yaml_scalar(item)),
+            }
+        }
+        return Some(serde_json::Value::Array(items));
+    }
+
+    let mut map = serde_json::Map::new();
+    while let Some(&(ind, line)) = lines.get(*pos) {
+        if ind < indent {
+            break;
+        }
+        *pos += 1;
+        if ind > indent {
+            // Deeper than anything we expect here; skip it
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+        let value = strip_yaml_comment(value);
+        if !value.is_empty() {
+            map.insert(key, parse_yaml_scalar(value));
+            continue;
+        }
+        // A nested block is indented further, except lists, which YAML may
+        // put at the key's own indent
+        match lines.get(*pos) {
+            Some(&(next, l)) if next > indent || (next == indent && l.starts_with('-')) => {
+                if let Some(child) = parse_yaml_block(lines, pos, next) {
+                    map.insert(key, child);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(serde_json::Value::Object(map))
+}
+
+fn strip_yaml_comment(value: &str) -> &str {
+    let value = value.trim();
+    if value.starts_with('"') || value.starts_with('\'') {
+        return value;
+    }
+    value.split_once(" #").map_or(value, |(v, _)| v).trim()
+}
+
+fn parse_yaml_scalar(value: &str) -> serde_json::Value {
+    let value = strip_yaml_comment(value);
+    if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return serde_json::Value::Array(
+            inner
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(parse_yaml_scalar)
+                .collect(),
+        );
+    }
+    match value.trim_matches(|c| c == '"' || c == '\'') {
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        v => v
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .unwrap_or_else(|_| serde_json::Value::String(v.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A fresh project directory under the system temp dir holding `files`.
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("promptctl-conventions-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for (path, content) in files {
+            fs::write(root.join(path), content).unwrap();
+        }
+        root
+    }
+
+    fn rules(conventions: &[Convention], language: &str) -> Vec<String> {
+        render(conventions, language)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|l| l.strip_prefix("- "))
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn later_editorconfig_sections_override_earlier_ones() {
+        let root = project(
+            "editorconfig",
+            &[(
+                ".editorconfig",
+                "root = true\n\n[*]\nindent_style = space\nindent_size = 4\n\n[*.go]\nindent_style = tab\n",
+            )],
+        );
+        let conventions = detect(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(rules(&conventions, "go"), ["Indent size: 4", "Indent with tabs"]);
+        assert_eq!(rules(&conventions, "rust"), ["Indent with spaces", "Indent size: 4"]);
+    }
+
+    #[test]
+    fn editorconfig_globs_map_to_each_language_once() {
+        assert_eq!(editorconfig_languages("*.{ts,js,tsx,ts}"), Some(vec!["javascript", "typescript"]));
+        assert_eq!(editorconfig_languages("**"), Some(Vec::new()));
+        assert_eq!(editorconfig_languages("*.md"), None);
+    }
+
+    #[test]
+    fn parses_json_and_block_yaml() {
+        let json = parse_json_or_yaml("{\n  // comment\n  \"semi\": false\n}").unwrap();
+        assert_eq!(json, serde_json::json!({ "semi": false }));
+
+        let yaml = parse_json_or_yaml(
+            "---\n\
+             extends: [eslint:recommended, prettier]\n\
+             rules:\n\
+             \x20 quotes: [error, single]  # project style\n\
+             \x20 max-len:\n\
+             \x20   - warn\n\
+             \x20   - code: 100\n\
+             env:\n\
+             - browser\n\
+             root: true\n",
+        )
+        .unwrap();
+        assert_eq!(
+            yaml,
+            serde_json::json!({
+                "extends": ["eslint:recommended", "prettier"],
+                "rules": {
+                    "quotes": ["error", "single"],
+                    "max-len": ["warn", { "code": 100 }],
+                },
+                "env": ["browser"],
+                "root": true,
+            })
+        );
+
+        assert_eq!(parse_json_or_yaml("# only a comment\n"), None);
+    }
+
+    #[test]
+    fn golangci_ignores_lists_under_linter_settings() {
+        let root = project(
+            "golangci",
+            &[(
+                ".golangci.yml",
+                "version: \"2\"\n\
+                 linters:\n\
+                 \x20 default: standard\n\
+                 \x20 enable:\n\
+                 \x20   - gocritic\n\
+                 \x20   - revive # style\n\
+                 \x20 disable: [unused]\n\
+                 \x20 settings:\n\
+                 \x20   gocritic:\n\
+                 \x20     enable:\n\
+                 \x20       - hugeParam\n\
+                 \x20     disable:\n\
+                 \x20       - ifElseChain\n\
+                 formatters:\n\
+                 \x20 enable:\n\
+                 \x20 - gofumpt\n",
+            )],
+        );
+        let conventions = detect(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            rules(&conventions, "go"),
+            [
+                "Default linters: standard",
+                "Enabled: `gocritic`, `revive`, `gofumpt`",
+                "Disabled: `unused`",
+            ]
+        );
+    }
+}
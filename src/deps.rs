@@ -0,0 +1,324 @@
+//! Dependency policy rendering and manifest checks.
+//!
+//! The `[dependencies.<lang>]` tables in `.promptctl.toml` tell agents which
+//! packages the team has approved. This module renders that policy into the
+//! skillset and checks dependency manifests (Cargo.toml, package.json, go.mod)
+//! against it for `promptctl check-deps`.
+
+use crate::config::DependencyPolicy;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Languages whose policy applies to a manifest, by file name.
+pub fn manifest_languages(path: &Path) -> &'static [&'static str] {
+    match path.file_name().and_then(|n| n.to_str()) {
+        Some("Cargo.toml") => &["rust"],
+        Some("package.json") => &["typescript", "javascript"],
+        Some("go.mod") => &["go"],
+        _ => &[],
+    }
+}
+
+/// Read the dependency names declared in a manifest.
+pub fn read_manifest(path: &Path) -> Result<Vec<String>, std::io::Error> {
+    let content = fs::read_to_string(path)?;
+    Ok(parse_manifest(path, &content))
+}
+
+/// Parse manifest content as the kind of manifest `path` names. The content
+/// may come from elsewhere, e.g. a snapshot taken before an edit.
+pub fn parse_manifest(path: &Path, content: &str) -> Vec<String> {
+    match path.file_name().and_then(|n| n.to_str()) {
+        Some("Cargo.toml") => cargo_dependencies(content),
+        Some("package.json") => node_dependencies(content),
+        Some("go.mod") => go_dependencies(content),
+        _ => Vec::new(),
+    }
+}
+
+fn cargo_dependencies(content: &str) -> Vec<String> {
+    let Ok(table) = toml::from_str::<toml::Table>(content) else {
+        return Vec::new();
+    };
+
+    let mut tables: Vec<&toml::Value> = Vec::new();
+    for key in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        tables.extend(table.get(key));
+        tables.extend(table.get("workspace").and_then(|w| w.get(key)));
+    }
+    if let Some(targets) = table.get("target").and_then(toml::Value::as_table) {
+        for target in targets.values() {
+            for key in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                tables.extend(target.get(key));
+            }
+        }
+    }
+
+    let mut deps = Vec::new();
+    for t in tables.iter().filter_map(|t| t.as_table()) {
+        for (name, spec) in t {
+            // Renamed dependencies declare the real crate in `package`
+            let real = spec.get("package").and_then(toml::Value::as_str).unwrap_or(name);
+            deps.push(real.to_string());
+        }
+    }
+    deps.sort();
+    deps.dedup();
+    deps
+}
+
+fn node_dependencies(content: &str) -> Vec<String> {
+    let Ok(pkg) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let mut deps = Vec::new();
+    for key in [
+        "dependencies",
+        "devDependencies",
+        "peerDependencies",
+        "optionalDependencies",
+    ] {
+        if let Some(map) = pkg[key].as_object() {
+            deps.extend(map.keys().cloned());
+        }
+    }
+    deps.sort();
+    deps.dedup();
+    deps
+}
+
+/// Modules required by a go.mod. Requirements marked `// indirect` are only
+/// needed by other dependencies, so they are left out; `replace` directives
+/// do not add requirements either.
+fn go_dependencies(content: &str) -> Vec<String> {
+    let mut deps = Vec::new();
+    let mut in_block = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with("require (") {
+            in_block = true;
+            continue;
+        }
+        if in_block && line == ")" {
+            in_block = false;
+            continue;
+        }
+        let spec = if in_block {
+            line
+        } else if let Some(rest) = line.strip_prefix("require ") {
+            rest
+        } else {
+            continue;
+        };
+        if let Some(module) = spec.split_whitespace().next()
+            && !module.starts_with("//")
+            && !spec.ends_with("// indirect")
+        {
+            deps.push(module.to_string());
+        }
+    }
+    deps.sort();
+    deps.dedup();
+    deps
+}
+
+/// Dependencies in `declared` that the `base` copy of the manifest at `path`
+/// does not declare.
+pub fn added_since(path: &Path, declared: &[String], base: &str) -> Vec<String> {
+    let before = parse_manifest(path, base);
+    declared.iter().filter(|d| !before.contains(d)).cloned().collect()
+}
+
+/// A dependency that breaks the policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// On the denied list, with the preferred alternative if one is configured
+    Denied { name: String, instead: Option<String> },
+    /// Not on a non-empty allowed list
+    Unlisted { name: String },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Denied {
+                name,
+                instead: Some(alt),
+            } => write!(f, "`{name}` is denied — use `{alt}` instead"),
+            Violation::Denied { name, instead: None } => write!(f, "`{name}` is denied"),
+            Violation::Unlisted { name } => write!(f, "`{name}` is not on the allowed list"),
+        }
+    }
+}
+
+/// Check dependency names against a policy.
+pub fn check(deps: &[String], policy: &DependencyPolicy) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for name in deps {
+        let instead = policy.preferred.get(name).cloned();
+        if policy.denied.iter().any(|d| matches_entry(d, name)) || instead.is_some() {
+            violations.push(Violation::Denied {
+                name: name.clone(),
+                instead,
+            });
+        } else if !policy.allowed.is_empty() && !policy.allowed.iter().any(|a| matches_entry(a, name)) {
+            violations.push(Violation::Unlisted { name: name.clone() });
+        }
+    }
+    violations
+}
+
+fn matches_entry(entry: &str, name: &str) -> bool {
+    match entry.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => entry == name,
+    }
+}
+
+/// Render a policy as markdown for the skillset's Dependencies section.
+pub fn render_policy(policy: &DependencyPolicy) -> String {
+    let list = |items: &[String]| {
+        items
+            .iter()
+            .map(|i| format!("`{i}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut out = String::from("### Dependency Policy\n\n");
+    out.push_str("This project restricts which packages may be used:\n\n");
+    if !policy.allowed.is_empty() {
+        out.push_str(&format!("- **Allowed:** {}\n", list(&policy.allowed)));
+    }
+    if !policy.denied.is_empty() {
+        out.push_str(&format!("- **Denied:** {} — never add these\n", list(&policy.denied)));
+    }
+    if !policy.preferred.is_empty() {
+        let mut prefs: Vec<_> = policy.preferred.iter().collect();
+        prefs.sort();
+        let prefs: Vec<String> = prefs
+            .into_iter()
+            .map(|(avoid, instead)| format!("use `{instead}`, not `{avoid}`"))
+            .collect();
+        out.push_str(&format!("- **Preferred:** {}\n", prefs.join("; ")));
+    }
+    if !policy.allowed.is_empty() {
+        out.push_str("- Ask before adding any package that is not on the allowed list.\n");
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(deps: &[&str]) -> Vec<String> {
+        deps.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_cargo_dependencies() {
+        let manifest = r#"
+[package]
+name = "app"
+
+[dependencies]
+serde = "1"
+json = { package = "serde_json", version = "1" }
+
+[dev-dependencies]
+tempfile = "3"
+
+[target.'cfg(unix)'.dependencies]
+nix = "0.29"
+
+[workspace.dependencies]
+tokio = "1"
+"#;
+        assert_eq!(
+            parse_manifest(Path::new("Cargo.toml"), manifest),
+            names(&["nix", "serde", "serde_json", "tempfile", "tokio"])
+        );
+        assert!(parse_manifest(Path::new("Cargo.toml"), "[dependencies").is_empty());
+    }
+
+    #[test]
+    fn parses_node_dependencies() {
+        let manifest = r#"{
+  "name": "web",
+  "dependencies": { "react": "^19.0.0" },
+  "devDependencies": { "vitest": "^3.0.0", "react": "^19.0.0" },
+  "peerDependencies": { "react-dom": "^19.0.0" },
+  "scripts": { "test": "vitest" }
+}"#;
+        assert_eq!(
+            parse_manifest(Path::new("web/package.json"), manifest),
+            names(&["react", "react-dom", "vitest"])
+        );
+    }
+
+    #[test]
+    fn parses_go_requirements() {
+        let manifest = "module example.com/app
+
+go 1.22
+
+require github.com/spf13/cobra v1.8.0
+
+require (
+\t// pinned for the API below
+\tgithub.com/pkg/errors v0.9.1
+\tgolang.org/x/sync v0.7.0 // indirect
+)
+
+replace github.com/pkg/errors => github.com/acme/errors v0.9.2
+
+replace (
+\tgolang.org/x/net => golang.org/x/net v0.25.0
+)
+";
+        assert_eq!(
+            parse_manifest(Path::new("go.mod"), manifest),
+            names(&["github.com/pkg/errors", "github.com/spf13/cobra"])
+        );
+    }
+
+    #[test]
+    fn only_dependencies_missing_from_the_base_are_added() {
+        let before = "[dependencies]\nserde = \"1\"\n";
+        let after = "[dependencies]\nserde = \"1.0.200\"\nanyhow = \"1\"\n";
+        let path = Path::new("Cargo.toml");
+        let declared = parse_manifest(path, after);
+        assert_eq!(added_since(path, &declared, before), names(&["anyhow"]));
+        // A missing base means the manifest is new
+        assert_eq!(added_since(path, &declared, ""), names(&["anyhow", "serde"]));
+
+        let policy = DependencyPolicy {
+            denied: names(&["serde"]),
+            preferred: [("anyhow".to_string(), "thiserror".to_string())].into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            check(&added_since(path, &declared, before), &policy),
+            [Violation::Denied {
+                name: "anyhow".to_string(),
+                instead: Some("thiserror".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn allowed_entries_match_by_prefix() {
+        let policy = DependencyPolicy {
+            allowed: names(&["github.com/acme/*", "golang.org/x/sync"]),
+            ..Default::default()
+        };
+        let deps = names(&["github.com/acme/log", "golang.org/x/sync", "golang.org/x/net"]);
+        assert_eq!(
+            check(&deps, &policy),
+            [Violation::Unlisted {
+                name: "golang.org/x/net".to_string()
+            }]
+        );
+    }
+}
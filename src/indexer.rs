@@ -1,12 +1,13 @@
 //! Project indexing to analyze codebase structure and detect technologies.
 
+use crate::conventions::{self, Convention};
+use crate::deps;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Detected project information
 #[derive(Debug, Clone, Default)]
-#[allow(dead_code)]
 pub struct ProjectIndex {
     /// Root directory of the project
     pub root: PathBuf,
@@ -18,6 +19,14 @@ pub struct ProjectIndex {
     pub config_files: Vec<PathBuf>,
     /// Directory structure summary
     pub structure: ProjectStructure,
+    /// Dependency names declared in the root manifests
+    pub dependencies: Vec<String>,
+    /// Style rules read from formatter and linter configs
+    pub conventions: Vec<Convention>,
+    /// Files holding secrets or private keys that agents must never read
+    pub sensitive_files: Vec<PathBuf>,
+    /// Extension → language mappings from config, checked before the built-ins
+    custom_extensions: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -48,6 +57,20 @@ pub enum FrameworkCategory {
     Other,
 }
 
+impl FrameworkCategory {
+    /// Words a skillset section about this kind of framework would use
+    pub const fn terms(&self) -> &'static [&'static str] {
+        match self {
+            FrameworkCategory::Web => &["web", "http", "server", "api", "async"],
+            FrameworkCategory::Cli => &["cli", "command", "args"],
+            FrameworkCategory::Testing => &["test", "testing", "mock"],
+            FrameworkCategory::Build => &["build", "tooling"],
+            FrameworkCategory::Database => &["database", "sql", "query", "db"],
+            FrameworkCategory::Library | FrameworkCategory::Other => &[],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ProjectStructure {
     pub has_src: bool,
@@ -60,14 +83,22 @@ pub struct ProjectStructure {
 impl ProjectIndex {
     /// Index a project starting from the given root directory
     pub fn scan(root: &Path) -> Self {
+        Self::scan_with_extensions(root, HashMap::new())
+    }
+
+    /// Index a project, also mapping the given extensions to custom languages
+    pub fn scan_with_extensions(root: &Path, custom_extensions: HashMap<String, String>) -> Self {
         let mut index = Self {
             root: root.to_path_buf(),
+            custom_extensions,
             ..Default::default()
         };
 
         index.scan_directory(root, 0);
         index.detect_frameworks(root);
+        index.read_dependencies(root);
         index.scan_structure(root);
+        index.conventions = conventions::detect(root);
 
         index
     }
@@ -87,6 +118,15 @@ impl ProjectIndex {
             let path = entry.path();
             let name = entry.file_name().to_string_lossy().to_string();
 
+            // Classify secrets before the hidden-file skip so `.env` is caught.
+            // Code files still count toward language detection.
+            if path.is_file() && is_sensitive_file(&path) {
+                self.sensitive_files.push(path.clone());
+                if !self.is_source_file(&path) {
+                    continue;
+                }
+            }
+
             // Skip hidden and common ignored directories
             if name.starts_with('.')
                 || matches!(
@@ -111,37 +151,23 @@ impl ProjectIndex {
         }
     }
 
-    fn process_file(&mut self, path: &Path) {
-        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-            let (lang, ext_str) = match ext {
-                "rs" => ("rust", "rs"),
-                "go" => ("go", "go"),
-                "leo" => ("leo", "leo"),
-                "aleo" => ("leo", "aleo"),
-                "py" | "pyi" => ("python", ext),
-                "ts" | "tsx" => ("typescript", ext),
-                "js" | "jsx" | "mjs" | "cjs" => ("javascript", ext),
-                "rb" => ("ruby", "rb"),
-                "java" => ("java", "java"),
-                "kt" | "kts" => ("kotlin", ext),
-                "swift" => ("swift", "swift"),
-                "c" | "h" => ("c", ext),
-                "cpp" | "cc" | "cxx" | "hpp" => ("cpp", ext),
-                "zig" => ("zig", "zig"),
-                "ex" | "exs" => ("elixir", ext),
-                "erl" | "hrl" => ("erlang", ext),
-                "hs" => ("haskell", "hs"),
-                "ml" | "mli" => ("ocaml", ext),
-                "scala" | "sc" => ("scala", ext),
-                "clj" | "cljs" | "cljc" => ("clojure", ext),
-                "lua" => ("lua", "lua"),
-                "sh" | "bash" | "zsh" => ("shell", ext),
-                "sql" => ("sql", "sql"),
-                "proto" => ("protobuf", "proto"),
-                "graphql" | "gql" => ("graphql", ext),
-                _ => return,
-            };
+    fn is_source_file(&self, path: &Path) -> bool {
+        path.extension().and_then(|e| e.to_str()).is_some_and(|ext| {
+            self.custom_extensions.contains_key(&ext.to_lowercase())
+                || language_for_extension(ext).is_some()
+        })
+    }
 
+    fn process_file(&mut self, path: &Path) {
+        let custom_or_builtin = |ext: &str| {
+            self.custom_extensions
+                .get(&ext.to_lowercase())
+                .cloned()
+                .or_else(|| language_for_extension(ext).map(str::to_string))
+        };
+        if let Some(ext) = path.extension().and_then(|e| e.to_str())
+            && let Some(lang) = custom_or_builtin(ext)
+        {
             let info = self
                 .languages
                 .entry(lang.to_string())
@@ -151,7 +177,7 @@ impl ProjectIndex {
                 });
 
             info.file_count += 1;
-            info.extensions.insert(ext_str.to_string());
+            info.extensions.insert(ext.to_string());
         }
 
         // Track config files
@@ -163,17 +189,17 @@ impl ProjectIndex {
 
     fn detect_frameworks(&mut self, root: &Path) {
         // Rust frameworks
-        if root.join("Cargo.toml").exists() {
-            if let Ok(content) = fs::read_to_string(root.join("Cargo.toml")) {
-                self.detect_rust_frameworks(&content, root);
-            }
+        if root.join("Cargo.toml").exists()
+            && let Ok(content) = fs::read_to_string(root.join("Cargo.toml"))
+        {
+            self.detect_rust_frameworks(&content, root);
         }
 
         // Go frameworks
-        if root.join("go.mod").exists() {
-            if let Ok(content) = fs::read_to_string(root.join("go.mod")) {
-                self.detect_go_frameworks(&content, root);
-            }
+        if root.join("go.mod").exists()
+            && let Ok(content) = fs::read_to_string(root.join("go.mod"))
+        {
+            self.detect_go_frameworks(&content, root);
         }
 
         // Leo/Aleo project
@@ -182,10 +208,10 @@ impl ProjectIndex {
         }
 
         // Node.js/JavaScript frameworks
-        if root.join("package.json").exists() {
-            if let Ok(content) = fs::read_to_string(root.join("package.json")) {
-                self.detect_node_frameworks(&content, root);
-            }
+        if root.join("package.json").exists()
+            && let Ok(content) = fs::read_to_string(root.join("package.json"))
+        {
+            self.detect_node_frameworks(&content, root);
         }
 
         // Python frameworks
@@ -194,6 +220,16 @@ impl ProjectIndex {
         }
     }
 
+    fn read_dependencies(&mut self, root: &Path) {
+        for manifest in ["Cargo.toml", "package.json", "go.mod"] {
+            if let Ok(deps) = deps::read_manifest(&root.join(manifest)) {
+                self.dependencies.extend(deps);
+            }
+        }
+        self.dependencies.sort();
+        self.dependencies.dedup();
+    }
+
     fn detect_leo_project(&mut self, root: &Path) {
         // Add Leo language if not already detected
         let lang = self
@@ -207,10 +243,10 @@ impl ProjectIndex {
         // Try to detect version from program.json
         if let Ok(content) = fs::read_to_string(root.join("program.json")) {
             // Look for "version" field in program.json
-            if let Some(line) = content.lines().find(|l| l.contains("\"version\"")) {
-                if let Some(ver) = line.split('"').nth(3) {
-                    lang.version = Some(ver.to_string());
-                }
+            if let Some(line) = content.lines().find(|l| l.contains("\"version\""))
+                && let Some(ver) = line.split('"').nth(3)
+            {
+                lang.version = Some(ver.to_string());
             }
         }
 
@@ -223,12 +259,11 @@ impl ProjectIndex {
 
     fn detect_rust_frameworks(&mut self, content: &str, root: &Path) {
         // Detect version
-        if let Some(lang) = self.languages.get_mut("rust") {
-            if let Some(line) = content.lines().find(|l| l.contains("rust-version")) {
-                if let Some(ver) = line.split('"').nth(1) {
-                    lang.version = Some(ver.to_string());
-                }
-            }
+        if let Some(lang) = self.languages.get_mut("rust")
+            && let Some(line) = content.lines().find(|l| l.contains("rust-version"))
+            && let Some(ver) = line.split('"').nth(1)
+        {
+            lang.version = Some(ver.to_string());
         }
 
         let frameworks_to_detect = [
@@ -261,10 +296,10 @@ impl ProjectIndex {
 
     fn detect_go_frameworks(&mut self, content: &str, root: &Path) {
         // Detect version
-        if let Some(lang) = self.languages.get_mut("go") {
-            if let Some(line) = content.lines().find(|l| l.starts_with("go ")) {
-                lang.version = Some(line.trim_start_matches("go ").trim().to_string());
-            }
+        if let Some(lang) = self.languages.get_mut("go")
+            && let Some(line) = content.lines().find(|l| l.starts_with("go "))
+        {
+            lang.version = Some(line.trim_start_matches("go ").trim().to_string());
         }
 
         let frameworks_to_detect = [
@@ -376,23 +411,23 @@ impl ProjectIndex {
     fn scan_structure(&mut self, root: &Path) {
         if let Ok(entries) = fs::read_dir(root) {
             for entry in entries.filter_map(Result::ok) {
-                if let Ok(ft) = entry.file_type() {
-                    if ft.is_dir() {
-                        let name = entry.file_name().to_string_lossy().to_string();
-                        if !name.starts_with('.') {
-                            self.structure.top_level_dirs.push(name.clone());
-
-                            match name.as_str() {
-                                "src" | "lib" => self.structure.has_src = true,
-                                "test" | "tests" | "spec" | "__tests__" => {
-                                    self.structure.has_tests = true
-                                }
-                                "docs" | "doc" | "documentation" => self.structure.has_docs = true,
-                                ".github" | ".gitlab-ci" | ".circleci" => {
-                                    self.structure.has_ci = true
-                                }
-                                _ => {}
+                if let Ok(ft) = entry.file_type()
+                    && ft.is_dir()
+                {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if !name.starts_with('.') {
+                        self.structure.top_level_dirs.push(name.clone());
+
+                        match name.as_str() {
+                            "src" | "lib" => self.structure.has_src = true,
+                            "test" | "tests" | "spec" | "__tests__" => {
+                                self.structure.has_tests = true
+                            }
+                            "docs" | "doc" | "documentation" => self.structure.has_docs = true,
+                            ".github" | ".gitlab-ci" | ".circleci" => {
+                                self.structure.has_ci = true
                             }
+                            _ => {}
                         }
                     }
                 }
@@ -415,6 +450,22 @@ impl ProjectIndex {
         }
     }
 
+    /// Sensitive files as sorted, root-relative paths with forward slashes.
+    pub fn sensitive_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .sensitive_files
+            .iter()
+            .map(|p| {
+                p.strip_prefix(&self.root)
+                    .unwrap_or(p)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
     /// Get the primary language (most files)
     pub fn primary_language(&self) -> Option<&LanguageInfo> {
         self.languages.values().max_by_key(|l| l.file_count)
@@ -427,7 +478,7 @@ impl ProjectIndex {
         // Languages
         if !self.languages.is_empty() {
             let mut langs: Vec<_> = self.languages.values().collect();
-            langs.sort_by(|a, b| b.file_count.cmp(&a.file_count));
+            langs.sort_by_key(|l| std::cmp::Reverse(l.file_count));
 
             let lang_strs: Vec<String> = langs
                 .iter()
@@ -468,6 +519,48 @@ impl ProjectIndex {
     }
 }
 
+/// Languages the scan detects, with their file extensions.
+const LANGUAGE_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("rust", &["rs"]),
+    ("go", &["go"]),
+    ("leo", &["leo", "aleo"]),
+    ("solidity", &["sol"]),
+    ("python", &["py", "pyi"]),
+    ("typescript", &["ts", "tsx"]),
+    ("javascript", &["js", "jsx", "mjs", "cjs"]),
+    ("ruby", &["rb"]),
+    ("java", &["java"]),
+    ("kotlin", &["kt", "kts"]),
+    ("swift", &["swift"]),
+    ("c", &["c", "h"]),
+    ("cpp", &["cpp", "cc", "cxx", "hpp"]),
+    ("zig", &["zig"]),
+    ("elixir", &["ex", "exs"]),
+    ("erlang", &["erl", "hrl"]),
+    ("haskell", &["hs"]),
+    ("ocaml", &["ml", "mli"]),
+    ("scala", &["scala", "sc"]),
+    ("clojure", &["clj", "cljs", "cljc"]),
+    ("lua", &["lua"]),
+    ("shell", &["sh", "bash", "zsh"]),
+    ("sql", &["sql"]),
+    ("protobuf", &["proto"]),
+    ("graphql", &["graphql", "gql"]),
+];
+
+/// Map a file extension to the language it belongs to.
+pub fn language_for_extension(ext: &str) -> Option<&'static str> {
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .find(|(_, exts)| exts.contains(&ext))
+        .map(|(lang, _)| *lang)
+}
+
+/// Whether `name` is a language the scan can detect.
+pub fn is_detected_language(name: &str) -> bool {
+    LANGUAGE_EXTENSIONS.iter().any(|(lang, _)| *lang == name)
+}
+
 fn is_config_file(name: &str) -> bool {
     matches!(
         name,
@@ -483,7 +576,66 @@ fn is_config_file(name: &str) -> bool {
             | "Makefile"
             | "Dockerfile"
             | "docker-compose.yml"
-            | ".env"
+            | "rustfmt.toml"
+            | "clippy.toml"
+            | "eslint.config.js"
+            | "eslint.config.mjs"
+            | "biome.json"
+            | "ruff.toml"
+            | "foundry.toml"
             | ".env.example"
     )
 }
+
+/// Whether a file holds secrets: dotenv files, private keys, certificates,
+/// keystores, `secrets.*` and Foundry/Aleo private key files. Example and
+/// template dotenv files are not considered sensitive. Name-based matches only
+/// apply to data files, so `src/private_key.rs` or `secrets.ts` are code.
+pub fn is_sensitive_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let is_template = [".example", ".sample", ".template", ".dist"]
+        .iter()
+        .any(|s| name.ends_with(s));
+    if name == ".env" || name.starts_with(".env.") || name.ends_with(".env") {
+        return !is_template;
+    }
+
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if matches!(
+        ext.as_str(),
+        "pem" | "key" | "p12" | "pfx" | "jks" | "keystore" | "privkey"
+    ) {
+        return true;
+    }
+
+    // Extensions a secrets or key file is stored with; anything else is code
+    let data_file = matches!(
+        ext.as_str(),
+        "" | "json" | "txt" | "yaml" | "yml" | "toml" | "ini" | "env"
+    );
+
+    if data_file && (name.starts_with("secrets.") || name.starts_with("secret.")) {
+        return !is_template;
+    }
+
+    if matches!(name.as_str(), "id_rsa" | "id_ecdsa" | "id_ed25519" | "id_dsa") {
+        return true;
+    }
+
+    // Foundry `cast wallet` keystores and Aleo/Leo private key files
+    let in_keystore_dir = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .is_some_and(|d| matches!(d.to_string_lossy().as_ref(), "keystore" | "keystores"));
+    let key_name = ["private_key", "private-key", "privatekey"]
+        .iter()
+        .any(|k| name.contains(k));
+    data_file && (in_keystore_dir || key_name)
+}
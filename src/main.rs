@@ -4,21 +4,26 @@ mod agents;
 mod cli;
 mod clipboard;
 mod config;
+mod conventions;
+mod deps;
 mod indexer;
 mod prompt_builder;
 mod prompts;
 mod roles;
+mod stats;
+mod tokens;
 
 use agents::Agent;
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, InitOptions, SkillsetArgs};
 use colored::Colorize;
-use config::{Config, PromptMode};
+use config::{Config, PromptMode, SectionAction};
 use indexer::ProjectIndex;
-use prompt_builder::{PromptBuilder, PromptSize};
-use roles::Role;
+use prompt_builder::{PromptBuilder, PromptSize, Section, StructuredPrompt, section_breakdown};
+use roles::{Persona, Role};
 use std::path::PathBuf;
 use std::process::ExitCode;
+use tokens::TokenCounter;
 
 fn main() -> ExitCode {
     let cli = Cli::parse();
@@ -36,14 +41,21 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     match cli.command {
         Commands::Init {
             agent,
+            options,
+            selection,
+        } => cmd_init(&agent, &options, &SkillsetOptions::from_args(&selection)?),
+        Commands::Show {
+            language,
             role,
-            path,
-            force,
-            dry_run,
-            global,
-        } => cmd_init(&agent, &role, path.as_deref(), force, dry_run, global),
-        Commands::Show { language, role } => cmd_show(&language, role.as_deref()),
+            explain,
+            selection,
+        } => {
+            let options = SkillsetOptions::from_args(&selection)?;
+            cmd_show(&language, role.as_deref(), explain, &options)
+        }
         Commands::List => cmd_list(),
+        Commands::CheckDeps { files, base } => cmd_check_deps(&files, base.as_deref()),
+        Commands::Stats { path, json } => cmd_stats(path.as_deref(), json),
         Commands::Clean { agent, path } => cmd_clean(&agent, path.as_deref()),
     }
 }
@@ -52,12 +64,21 @@ fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
 
 fn cmd_init(
     agent_name: &str,
-    role_name: &str,
-    path: Option<&str>,
-    force: bool,
-    dry_run: bool,
-    global: bool,
+    init: &InitOptions,
+    options: &SkillsetOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let (role_name, path) = (init.role.as_deref(), init.path.as_deref());
+    let InitOptions {
+        force,
+        dry_run,
+        global,
+        strict,
+        subagents,
+        skills,
+        commands,
+        vscode_settings,
+        ..
+    } = *init;
     let agent = Agent::from_str(agent_name).ok_or_else(|| {
         format!(
             "unknown agent: '{agent_name}'. Supported: {}",
@@ -72,25 +93,36 @@ fn cmd_init(
     if agent == Agent::Raw {
         return Err("cannot init for 'raw' agent — pick a real agent.".into());
     }
-
-    let role = Role::from_str(role_name).ok_or_else(|| {
-        format!(
-            "unknown role: '{role_name}'. Available: {}",
-            Role::all()
-                .iter()
-                .map(Role::name)
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
-    })?;
+    if subagents && agent != Agent::Claude {
+        return Err("--subagents is only supported for claude.".into());
+    }
+    if skills && agent != Agent::Claude {
+        return Err("--skills is only supported for claude.".into());
+    }
+    if commands && !agents::supports_commands(agent) {
+        return Err("--commands is only supported for claude, copilot and cursor.".into());
+    }
+    if vscode_settings && agent != Agent::Copilot {
+        return Err("--vscode-settings is only supported for copilot.".into());
+    }
 
     let scan_path = path
         .map(PathBuf::from)
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
     // Step 1: Scan
-    let index = ProjectIndex::scan(&scan_path);
-    let config = Config::load()?;
+    let config = load_config()?;
+    // Without --role, keep the role recorded when the file was last generated
+    let role_name = match role_name {
+        Some(name) => name.to_string(),
+        None => agent
+            .resolve_path(&scan_path, global)
+            .and_then(|p| agents::recorded_role(&p))
+            .unwrap_or_else(|| Role::Developer.name().to_string()),
+    };
+    let role = Persona::resolve(&role_name, config.as_ref())?;
+    let options = &options.clone().with_role(&role);
+    let index = scan_project(&scan_path, config.as_ref());
 
     let languages: Vec<String> = index.languages.keys().map(|l| l.to_lowercase()).collect();
 
@@ -103,7 +135,7 @@ fn cmd_init(
         if !languages.is_empty() {
             let lang_display: Vec<String> = {
                 let mut langs: Vec<_> = index.languages.values().collect();
-                langs.sort_by(|a, b| b.file_count.cmp(&a.file_count));
+                langs.sort_by_key(|l| std::cmp::Reverse(l.file_count));
                 langs
                     .iter()
                     .map(|l| {
@@ -134,9 +166,65 @@ fn cmd_init(
         .map(|l| l.name.clone())
         .unwrap_or_default();
 
+    let budget = Budget {
+        limit: config
+            .as_ref()
+            .and_then(|c| c.token_budget(agent.name()))
+            .unwrap_or(agent.token_budget()),
+        counter: tokens::for_agent(agent),
+        strict,
+    };
+    let counter = budget.counter;
+    let init_args = format!("--role {}{}", role.name, options.to_args());
+
+    // Pre-build skillsets for each detected language, shrunk to fit the
+    // budget; `--strict` fails before anything is written
+    let mut skillsets = agents::Skillsets::default();
+    let mut degraded = Vec::new();
+    if agents::supports_hooks(agent) {
+        for lang in &languages {
+            let Some(fitted) = fit_skillset(lang, config.as_ref(), &index, options, &budget)? else {
+                continue;
+            };
+            if !fitted.dropped.is_empty() {
+                let tokens = counter.count(&fitted.text);
+                degraded.push((lang.clone(), fitted.dropped, tokens));
+                // Claude's hooks rebuild the skillset with `promptctl show`
+                let args = format!("--role {}{}", role.name, fitted.options.to_args());
+                skillsets.args.insert(lang.clone(), args);
+            }
+            skillsets.text.insert(lang.clone(), fitted.text);
+        }
+    }
+
     // Step 2: Build prompt content for the main agent instruction file
-    let content = build_agent_prompt(&primary_lang, &role, &index, config.as_ref())?;
-    let formatted = agent.format_prompt(&content, &primary_lang);
+    let mut content = build_agent_prompt(agent, &primary_lang, &role, &index, config.as_ref())?;
+    // Gemini imports the skillsets into GEMINI.md. A global GEMINI.md imports
+    // copies next to it in ~/.gemini, so it does not depend on this project.
+    let hooks_root = if agent == Agent::Gemini && global {
+        dirs::home_dir().ok_or("cannot find the home directory")?
+    } else {
+        scan_path.clone()
+    };
+    if agent == Agent::Gemini {
+        // Import exactly the skillset files install_gemini_hooks writes
+        let imported: Vec<&String> = languages.iter().filter(|lang| skillsets.text.contains_key(*lang)).collect();
+        content.push_str(&agents::gemini_imports(&imported, global));
+    }
+    let mut header_args = init_args.clone();
+    if subagents {
+        header_args.push_str(" --subagents");
+    }
+    if skills {
+        header_args.push_str(" --skills");
+    }
+    if commands {
+        header_args.push_str(" --commands");
+    }
+    if vscode_settings {
+        header_args.push_str(" --vscode-settings");
+    }
+    let formatted = agent.format_prompt(&content, &primary_lang, &header_args);
 
     if dry_run {
         let instr_path = agent
@@ -150,46 +238,115 @@ fn cmd_init(
 
         // Show hook files that would be created
         if agents::supports_hooks(agent) {
-            let hook_files = preview_hook_files(agent, &languages, &scan_path);
+            let hook_files = preview_hook_files(agent, &languages, &hooks_root, config.as_ref(), init);
             for f in &hook_files {
                 println!("  {} {}", "•".green(), f);
             }
         }
+        if subagents {
+            for name in prompt_roles(config.as_ref()) {
+                let p = scan_path.join(format!(".claude/agents/promptctl-{name}.md"));
+                println!("  {} {}", "•".green(), p.display());
+            }
+        }
+        if commands {
+            for name in prompt_roles(config.as_ref()) {
+                if let Some(p) = agents::command_path(&scan_path, agent, &name) {
+                    println!("  {} {}", "•".green(), p.display());
+                }
+            }
+        }
+        if !global
+            && !index.sensitive_files.is_empty()
+            && let Some(p) = agents::secret_exclusions_file(&scan_path, agent)
+        {
+            println!("  {} {}", "•".green(), p.display());
+        }
 
         println!();
         println!("{formatted}");
         return Ok(());
     }
 
+    // Check what init generates against the agent's token budget before
+    // writing anything, so `--strict` leaves the project untouched
+    let base_over = budget.over(agent.instruction_file(), &formatted)?;
+    let role_prompts = if subagents || commands || matches!(agent, Agent::Copilot | Agent::Roo) {
+        build_role_prompts(&languages, config.as_ref(), &index, options)?
+    } else {
+        Vec::new()
+    };
+    if subagents || commands {
+        for prompt in &role_prompts {
+            budget.over(&format!("{} role prompt", prompt.name), &prompt.body)?;
+        }
+    }
+    let scoped_rules = match config.as_ref() {
+        Some(c) if agent == Agent::Codex && !global => c.resolved_rules()?,
+        _ => Vec::new(),
+    };
+    for (path, content, _) in agents::codex_scoped_files(&scan_path, &scoped_rules) {
+        budget.over(&path.display().to_string(), &content)?;
+    }
+    // Skillsets Claude loads that are not files of their own: the SessionStart
+    // output, or each skill with its supporting files
+    let mut over_budget = Vec::new();
+    if agent == Agent::Claude {
+        let loaded: Vec<(String, String)> = if skills {
+            languages
+                .iter()
+                .filter_map(|lang| Some((format!("{lang} skill"), skillsets.text.get(lang)?.clone())))
+                .collect()
+        } else {
+            let session: Vec<&str> = languages
+                .iter()
+                .filter_map(|lang| skillsets.text.get(lang).map(String::as_str))
+                .collect();
+            vec![("SessionStart context".to_string(), session.join("\n\n"))]
+        };
+        for (what, text) in loaded {
+            if let Some(tokens) = budget.over(&what, &text)? {
+                over_budget.push((what, tokens));
+            }
+        }
+    }
+
     // Write the main instruction file
     let instr_path = agent.emit(&formatted, &scan_path, global, force)?;
-    let token_estimate = formatted.len() / 4;
     println!(
         "{} Wrote {} instructions to {}",
         "✓".green().bold(),
         agent.display_name().cyan(),
         instr_path.display()
     );
-    println!("{}", format!("  ~{token_estimate} tokens").dimmed());
+    println!(
+        "{}",
+        format!("  {}", token_label(counter.count(&formatted), counter)).dimmed()
+    );
+    if base_over.is_some() {
+        println!(
+            "  {} over {}'s ~{}-token budget — trim [rules] or [protect], or raise [budgets]",
+            "!".yellow().bold(),
+            agent.display_name(),
+            budget.limit
+        );
+    }
+
+    // Every other file init writes, measured once written
+    let mut written = Vec::new();
 
     // Step 3: Install hooks (if agent supports them)
     if agents::supports_hooks(agent) {
-        // Pre-build skillsets for each detected language
-        let mut skillsets = std::collections::HashMap::new();
-        for lang in &languages {
-            if let Ok(skillset) = build_skillset(lang, config.as_ref()) {
-                skillsets.insert(lang.clone(), skillset);
-            }
-        }
-
         let files = agents::install_agent_hooks(
-            &scan_path,
+            &hooks_root,
             agent,
             &languages,
-            role_name,
+            &init_args,
             &skillsets,
-            force,
+            config.as_ref(),
+            init,
         )?;
+        written.extend(files.iter().map(|f| f.path.clone()));
 
         if !files.is_empty() {
             println!();
@@ -207,6 +364,172 @@ fn cmd_init(
                 );
             }
         }
+        for (lang, dropped, tokens) in &degraded {
+            let names: Vec<&str> = dropped.iter().map(Section::name).collect();
+            println!(
+                "  {} {lang} skillset over the ~{}-token budget — dropped {} (now {})",
+                "!".yellow().bold(),
+                budget.limit,
+                names.join(", "),
+                token_label(*tokens, counter)
+            );
+        }
+    }
+
+    // Claude subagents, Copilot chat modes, Roo modes and role commands, one per role
+    if subagents {
+        let files = agents::install_claude_subagents(&scan_path, &role_prompts, &header_args, force)?;
+        written.extend(files.iter().map(|f| f.path.clone()));
+        println!();
+        println!("{} Installed Claude Code subagents:", "✓".green().bold());
+        for f in &files {
+            println!(
+                "  {} {} {}",
+                "•".green(),
+                f.path.display(),
+                format!("— {}", f.description).dimmed()
+            );
+        }
+    }
+    if agent == Agent::Copilot {
+        let mut files =
+            agents::install_copilot_chat_modes(&scan_path, &role_prompts, &header_args, force)?;
+        let settings_error = if vscode_settings {
+            match agents::install_vscode_settings(&scan_path, &role_prompts, &header_args, force) {
+                Ok(written) => {
+                    files.extend(written);
+                    None
+                }
+                Err(e) => Some(e),
+            }
+        } else {
+            None
+        };
+        written.extend(files.iter().map(|f| f.path.clone()));
+        println!();
+        println!("{} Installed Copilot chat modes:", "✓".green().bold());
+        for f in &files {
+            println!(
+                "  {} {} {}",
+                "•".green(),
+                f.path.display(),
+                format!("— {}", f.description).dimmed()
+            );
+        }
+        if let Some(e) = settings_error {
+            println!("  {} skipped VS Code settings: {e}", "!".yellow().bold());
+        }
+    }
+    if agent == Agent::Gemini {
+        match agents::install_gemini_settings(&hooks_root) {
+            Ok(files) => {
+                for f in &files {
+                    println!();
+                    println!(
+                        "{} {} {}",
+                        "✓".green().bold(),
+                        f.path.display(),
+                        format!("— {}", f.description).dimmed()
+                    );
+                }
+            }
+            Err(e) => {
+                println!();
+                println!("  {} skipped Gemini CLI settings: {e}", "!".yellow().bold());
+            }
+        }
+    }
+    if agent == Agent::Roo {
+        match agents::install_roo_modes(&scan_path, &role_prompts, &header_args, force) {
+            Ok(files) => {
+                written.extend(files.iter().map(|f| f.path.clone()));
+                println!();
+                println!("{} Installed Roo Code modes:", "✓".green().bold());
+                for f in &files {
+                    println!(
+                        "  {} {} {}",
+                        "•".green(),
+                        f.path.display(),
+                        format!("— {}", f.description).dimmed()
+                    );
+                }
+            }
+            Err(e) => {
+                println!();
+                println!("  {} skipped Roo Code modes: {e}", "!".yellow().bold());
+            }
+        }
+    }
+    if commands {
+        let files =
+            agents::install_role_commands(&scan_path, agent, &role_prompts, &header_args, force)?;
+        written.extend(files.iter().map(|f| f.path.clone()));
+        println!();
+        println!(
+            "{} Installed {} commands:",
+            "✓".green().bold(),
+            agent.display_name().cyan()
+        );
+        for f in &files {
+            println!(
+                "  {} {} {}",
+                "•".green(),
+                f.path.display(),
+                format!("— {}", f.description).dimmed()
+            );
+        }
+    }
+
+    // Codex has no hooks but reads nested AGENTS.md files for scoped rules
+    if agent == Agent::Codex && !global {
+        let files = agents::install_codex_scoped_rules(&scan_path, &scoped_rules, force)?;
+        written.extend(files.iter().map(|f| f.path.clone()));
+        if !files.is_empty() {
+            println!();
+            println!("{} Installed scoped rules:", "✓".green().bold());
+            for f in &files {
+                println!(
+                    "  {} {} {}",
+                    "•".green(),
+                    f.path.display(),
+                    format!("— {}", f.description).dimmed()
+                );
+            }
+        }
+    }
+
+    for path in written.iter().filter(|p| p.extension().is_some_and(|e| e == "md" || e == "mdc")) {
+        let text = std::fs::read_to_string(path)?;
+        let what = path.strip_prefix(&scan_path).unwrap_or(path).display().to_string();
+        if let Some(tokens) = budget.over(&what, &text)? {
+            over_budget.push((what, tokens));
+        }
+    }
+    if !over_budget.is_empty() {
+        println!();
+        println!(
+            "{} Over {}'s ~{}-token budget — use --size or --exclude-section, or raise [budgets]:",
+            "!".yellow().bold(),
+            agent.display_name(),
+            budget.limit
+        );
+        for (what, tokens) in &over_budget {
+            println!("  {} {what} ({})", "•".yellow(), token_label(*tokens, counter));
+        }
+    }
+
+    // Step 4: Keep sensitive files out of the agent's context
+    if !global && agents::supports_secret_exclusions(agent) {
+        let files = agents::install_secret_exclusions(&scan_path, agent, &index.sensitive_paths())?;
+        for f in &files {
+            println!();
+            println!(
+                "{} {} {}",
+                "✓".green().bold(),
+                f.path.display(),
+                format!("— {}", f.description).dimmed()
+            );
+        }
     }
 
     // Init .promptctl.toml if it doesn't exist
@@ -241,19 +564,24 @@ fn cmd_init(
 ///   1. Role prefix (persona)
 ///   2. Project context (detected languages, frameworks, structure)
 ///   3. Hallucination guardrails (generic, not language-specific)
+///   4. Protected paths from `[protect]` in `.promptctl.toml`
+///   5. Sensitive files the agent must never read (when any are detected)
+///   6. Path-scoped `[[rules]]` the agent has no native way to scope
 ///
 /// Language-specific skillsets are delivered via agent hooks (Cursor .mdc rules,
-/// Copilot .instructions.md, Claude session hooks) — NOT baked into the base file.
+/// Windsurf, Cline and Roo rules, Copilot .instructions.md, Gemini imports,
+/// Claude session hooks) — NOT baked into the base file.
 fn build_agent_prompt(
+    agent: Agent,
     _language: &str,
-    role: &Role,
+    role: &Persona,
     index: &ProjectIndex,
-    _config: Option<&Config>,
+    config: Option<&Config>,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let mut content = String::new();
 
     // 1. Role prefix
-    content.push_str(role.prompt_prefix());
+    content.push_str(&role.prefix);
 
     // 2. Project context
     let context = index.to_context_string();
@@ -282,9 +610,67 @@ fn build_agent_prompt(
     // 4. Generic hallucination guardrails (no language-specific ones — those live in skillsets)
     content.push_str(GENERIC_GUARDRAILS);
 
+    // 5. Protected paths from .promptctl.toml
+    if let Some(protect) = config.map(|c| &c.protect)
+        && !protect.paths.is_empty()
+    {
+        content.push_str("\n## Protected Paths\n\n");
+        content.push_str(
+            "Never create, edit, or delete files matching these paths by hand. \
+             They are generated or managed by tooling — change the source or generator instead:\n\n",
+        );
+        for glob in &protect.paths {
+            content.push_str(&format!("- `{glob}`\n"));
+        }
+        if let Some(reason) = &protect.reason {
+            content.push_str(&format!("\n{reason}\n"));
+        }
+    }
+
+    // 6. Sensitive files the agent must never touch
+    let sensitive = index.sensitive_paths();
+    if !sensitive.is_empty() {
+        content.push_str("\n## Sensitive Files\n\n");
+        content.push_str(
+            "These files hold secrets or private keys. Never read, print, copy, or edit them, \
+             and never paste their contents into code, logs, or commit messages:\n\n",
+        );
+        for p in sensitive.iter().take(MAX_LISTED_SENSITIVE) {
+            content.push_str(&format!("- `{p}`\n"));
+        }
+        if sensitive.len() > MAX_LISTED_SENSITIVE {
+            content.push_str(&format!(
+                "- …and {} more\n",
+                sensitive.len() - MAX_LISTED_SENSITIVE
+            ));
+        }
+        content.push_str("\nReference secrets through environment variables or the project's secret manager instead.\n");
+    }
+
+    // 7. Scoped rules this agent cannot scope natively
+    let rules = match config {
+        Some(c) => c.resolved_rules()?,
+        None => Vec::new(),
+    };
+    let unscoped = agents::unscoped_rules(&index.root, agent, &rules);
+    if !unscoped.is_empty() {
+        content.push_str("\n## Path-Specific Rules\n\n");
+        content.push_str("Apply each rule only when working on files matching its path.\n");
+        for rule in unscoped {
+            content.push_str(&format!(
+                "\n### {} (`{}`)\n\n{}\n",
+                rule.title, rule.glob, rule.content
+            ));
+        }
+    }
+
     Ok(content)
 }
 
+/// Cap on sensitive paths listed in the base file; the agent-native ignore
+/// files still receive the full list.
+const MAX_LISTED_SENSITIVE: usize = 20;
+
 /// Generic guardrails appended to every base instruction file.
 /// Language-specific guardrails are part of each language's skillset.
 const GENERIC_GUARDRAILS: &str = r#"## Hallucination Prevention
@@ -302,34 +688,112 @@ const GENERIC_GUARDRAILS: &str = r#"## Hallucination Prevention
 
 // ── show ─────────────────────────────────────────────────────────────────────
 
-fn cmd_show(language: &str, role: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
-    let config = Config::load()?;
+fn cmd_show(
+    language: &str,
+    role: Option<&str>,
+    explain: bool,
+    options: &SkillsetOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = load_config()?;
+    let index = scan_project(&std::env::current_dir().unwrap_or_default(), config.as_ref());
+    let role = role
+        .map(|name| Persona::resolve(name, config.as_ref()))
+        .transpose()?;
+    let options = &match &role {
+        Some(role) => options.clone().with_role(role),
+        None => options.clone(),
+    };
+
+    if explain {
+        return explain_skillset(language, config.as_ref(), &index, options);
+    }
 
     // Build the skillset: structured prompt + custom merge + language guardrails
-    let prompt = build_skillset(language, config.as_ref())?;
+    let prompt = build_skillset(language, config.as_ref(), &index, options)?;
 
-    if let Some(role_name) = role {
-        let role = Role::from_str(role_name).ok_or_else(|| {
-            format!(
-                "unknown role: '{role_name}'. Available: {}",
-                Role::all()
-                    .iter()
-                    .map(Role::name)
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        })?;
-        println!("{}", role.prompt_prefix());
+    if let Some(role) = role {
+        println!("{}", role.prefix);
     }
 
     println!("{prompt}");
     Ok(())
 }
 
+/// Print why smart filtering keeps or drops each section of a skillset.
+fn explain_skillset(
+    language: &str,
+    config: Option<&Config>,
+    index: &ProjectIndex,
+    options: &SkillsetOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let lang_lower = language.to_lowercase();
+    let structured = structured_skillset(&lang_lower, config).ok_or_else(|| {
+        format!("'{language}' has no structured skillset, so there is nothing to filter.")
+    })?;
+
+    if !options.sections.is_empty() {
+        return Err("--explain shows smart filtering, which --section bypasses.".into());
+    }
+    let size = options.size(&lang_lower, config);
+    let emphasis = options.emphasis.iter().copied().collect();
+    let mut decisions = structured.explain_smart(index, size, &emphasis);
+    for d in decisions.iter_mut().filter(|d| options.exclude.contains(&d.section)) {
+        d.included = false;
+        d.reason = "excluded with --exclude-section".to_string();
+    }
+    println!(
+        "{} {} ({} size, ~{} token budget)",
+        "Smart filtering for".bold(),
+        lang_lower.cyan(),
+        size.name(),
+        size.token_budget()
+    );
+    for d in &decisions {
+        let mark = if d.included { "✓".green() } else { "✗".red() };
+        println!(
+            "  {mark} {} {} {}",
+            d.title,
+            format!("[{}, ~{} tokens]", d.section.name(), d.tokens).dimmed(),
+            format!("— {}", d.reason).dimmed()
+        );
+        for m in &d.matches {
+            println!("      {}", m.dimmed());
+        }
+    }
+    let total: usize = decisions.iter().filter(|d| d.included).map(|d| d.tokens).sum();
+    let budget = size.token_budget();
+    println!();
+    println!(
+        "{}",
+        format!(
+            "{} of {} sections kept, ~{total} of ~{budget} tokens",
+            decisions.iter().filter(|d| d.included).count(),
+            decisions.len()
+        )
+        .dimmed()
+    );
+    if total > budget {
+        println!(
+            "{} ~{} tokens over budget: minimal-size and emphasised sections are always kept",
+            "!".yellow(),
+            total - budget
+        );
+    }
+    if structured.project_version(index).is_none()
+        && structured.sections.iter().any(|s| s.min_version.is_some())
+    {
+        println!(
+            "{} No {lang_lower} version detected, so every version-gated section is kept",
+            "!".yellow()
+        );
+    }
+    Ok(())
+}
+
 // ── list ─────────────────────────────────────────────────────────────────────
 
 fn cmd_list() -> Result<(), Box<dyn std::error::Error>> {
-    let config = Config::load()?;
+    let config = load_config()?;
 
     // Languages
     println!("{}", "Languages:".bold());
@@ -347,6 +811,15 @@ fn cmd_list() -> Result<(), Box<dyn std::error::Error>> {
                 println!("  {} {}{}", "•".yellow(), lang, desc.dimmed());
             }
         }
+        for lang in cfg.skillset_languages() {
+            let source = format!(" - from .promptctl/{lang}.toml");
+            println!("  {} {}{}", "•".yellow(), lang, source.dimmed());
+        }
+        for lang in cfg.docs_only_languages() {
+            if prompts::get_builtin_prompt(lang, None).is_none() {
+                println!("  {} {}{}", "•".yellow(), lang, " - from .promptctl/ docs".dimmed());
+            }
+        }
     }
 
     // Agents
@@ -372,6 +845,17 @@ fn cmd_list() -> Result<(), Box<dyn std::error::Error>> {
             format!("- {}", role.description()).dimmed()
         );
     }
+    if let Some(ref cfg) = config {
+        for name in cfg.custom_role_names() {
+            let desc = cfg
+                .custom_role(name)
+                .map(|(_, r)| r.description.as_str())
+                .filter(|d| !d.is_empty())
+                .map(|d| format!("- {d}"))
+                .unwrap_or_default();
+            println!("  {} {} {}", "•".yellow(), name.cyan(), desc.dimmed());
+        }
+    }
 
     println!();
     println!(
@@ -382,6 +866,155 @@ fn cmd_list() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+// ── check-deps ───────────────────────────────────────────────────────────────
+
+fn cmd_check_deps(files: &[String], base: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let config = load_config()?.unwrap_or_default();
+    if base.is_some() && files.len() != 1 {
+        return Err("--base needs exactly one manifest to compare against it.".into());
+    }
+
+    let manifests: Vec<PathBuf> = if files.is_empty() {
+        ["Cargo.toml", "package.json", "go.mod"]
+            .iter()
+            .map(PathBuf::from)
+            .filter(|p| p.exists())
+            .collect()
+    } else {
+        files.iter().map(PathBuf::from).collect()
+    };
+
+    let mut failed = 0;
+    for manifest in &manifests {
+        let policy = deps::manifest_languages(manifest)
+            .iter()
+            .find_map(|lang| config.dependency_policy(lang));
+        let Some(policy) = policy else {
+            continue;
+        };
+
+        let mut declared = deps::read_manifest(manifest)?;
+        if let Some(base) = base {
+            // A missing base means the manifest is new, so every dependency was added
+            declared = deps::added_since(manifest, &declared, &std::fs::read_to_string(base).unwrap_or_default());
+        }
+        let violations = deps::check(&declared, policy);
+        if violations.is_empty() {
+            println!("{} {}", "✓".green().bold(), manifest.display());
+            continue;
+        }
+
+        failed += violations.len();
+        println!("{} {}", "✗".red().bold(), manifest.display());
+        for v in &violations {
+            println!("  {} {v}", "•".red());
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "{failed} {} the [dependencies] policy in .promptctl.toml",
+            if failed == 1 { "dependency violates" } else { "dependencies violate" }
+        )
+        .into());
+    }
+    Ok(())
+}
+
+// ── stats ────────────────────────────────────────────────────────────────────
+
+fn cmd_stats(path: Option<&str>, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let scan_path = path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+    let config = load_config()?;
+    let index = scan_project(&scan_path, config.as_ref());
+
+    // Render what Claude's SessionStart hook would inject
+    let show = |args: &[String]| {
+        let argv = std::iter::once("promptctl".to_string()).chain(args.iter().cloned());
+        let Commands::Show {
+            language,
+            role,
+            selection,
+            ..
+        } = Cli::try_parse_from(argv).ok()?.command
+        else {
+            return None;
+        };
+        let role = role
+            .map(|name| Persona::resolve(&name, config.as_ref()))
+            .transpose()
+            .ok()?;
+        let mut options = SkillsetOptions::from_args(&selection).ok()?;
+        if let Some(role) = &role {
+            options = options.with_role(role);
+        }
+        let skillset = build_skillset(&language, config.as_ref(), &index, &options).ok()?;
+        match role {
+            Some(role) => Some(format!("{}\n{skillset}", role.prefix)),
+            None => Some(skillset),
+        }
+    };
+    let report = stats::collect(&scan_path, &show);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if report.is_empty() {
+        println!(
+            "{}",
+            "No promptctl files found. Use 'promptctl init <agent>' to set up your project."
+                .dimmed()
+        );
+        return Ok(());
+    }
+
+    for (i, agent) in report.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{} {}",
+            agent.agent.cyan().bold(),
+            format!("({})", agent.tokenizer).dimmed()
+        );
+        let width = agent.files.iter().map(|f| f.loads.len()).max().unwrap_or(0);
+        for file in &agent.files {
+            let loads = format!("{:width$}", file.loads);
+            println!(
+                "  {:>6}  {}  {}",
+                file.tokens,
+                if file.always { loads.yellow() } else { loads.normal() },
+                file.path
+            );
+            let largest: Vec<String> = file
+                .largest_sections
+                .iter()
+                .map(|s| format!("{} {}", s.title, s.tokens))
+                .collect();
+            if !largest.is_empty() {
+                println!(
+                    "  {:>6}  {}",
+                    "",
+                    format!("largest: {}", largest.join(", ")).dimmed()
+                );
+            }
+        }
+        println!(
+            "  {}",
+            format!(
+                "{} tokens on every request, up to {} when every hook loads",
+                agent.always_tokens(),
+                agent.total_tokens()
+            )
+            .dimmed()
+        );
+    }
+    Ok(())
+}
+
 // ── clean ────────────────────────────────────────────────────────────────────
 
 fn cmd_clean(agent_name: &str, path: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
@@ -395,11 +1028,11 @@ fn cmd_clean(agent_name: &str, path: Option<&str>) -> Result<(), Box<dyn std::er
     let mut removed = Vec::new();
 
     // Remove main instruction file
-    if let Some(instr_path) = agent.resolve_path(&scan_path, false) {
-        if instr_path.exists() {
-            std::fs::remove_file(&instr_path)?;
-            removed.push(instr_path);
-        }
+    if let Some(instr_path) = agent.resolve_path(&scan_path, false)
+        && instr_path.exists()
+    {
+        std::fs::remove_file(&instr_path)?;
+        removed.push(instr_path);
     }
 
     // Remove hooks
@@ -408,6 +1041,26 @@ fn cmd_clean(agent_name: &str, path: Option<&str>) -> Result<(), Box<dyn std::er
         removed.extend(hook_removed);
     }
 
+    // Remove role commands
+    removed.extend(agents::remove_role_commands(&scan_path, agent)?);
+
+    // Remove nested AGENTS.md files generated for scoped rules
+    if agent == Agent::Codex
+        && let Some(cfg) = load_config()?
+    {
+        removed.extend(agents::remove_codex_scoped_rules(&scan_path, &cfg.resolved_rules()?)?);
+    }
+
+    // Remove secret exclusions
+    if agents::supports_secret_exclusions(agent) {
+        let index = ProjectIndex::scan(&scan_path);
+        for p in agents::remove_secret_exclusions(&scan_path, agent, &index.sensitive_paths())? {
+            if !removed.contains(&p) {
+                removed.push(p);
+            }
+        }
+    }
+
     if removed.is_empty() {
         println!(
             "{}",
@@ -430,19 +1083,239 @@ fn cmd_clean(agent_name: &str, path: Option<&str>) -> Result<(), Box<dyn std::er
 
 // ── helpers ──────────────────────────────────────────────────────────────────
 
+/// Size tier and section selection for skillsets, from `--size`, `--section`
+/// and `--exclude-section`.
+#[derive(Debug, Clone, Default)]
+struct SkillsetOptions {
+    size: Option<PromptSize>,
+    sections: Vec<Section>,
+    exclude: Vec<Section>,
+    /// Roles whose addenda are appended and whose sections are kept whatever
+    /// the size tier; carried by `--role`
+    roles: Vec<String>,
+    emphasis: Vec<Section>,
+}
+
+impl SkillsetOptions {
+    fn from_args(args: &SkillsetArgs) -> Result<Self, String> {
+        let size = args
+            .size
+            .as_deref()
+            .map(|s| {
+                PromptSize::from_str(s)
+                    .ok_or_else(|| format!("unknown size: '{s}'. Available: minimal, compact, full"))
+            })
+            .transpose()?;
+        let parse_sections = |names: &[String]| {
+            names
+                .iter()
+                .map(|n| {
+                    Section::from_str(n).ok_or_else(|| {
+                        let all: Vec<&str> = Section::all().iter().map(Section::name).collect();
+                        format!("unknown section: '{n}'. Available: {}", all.join(", "))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(Self {
+            size,
+            sections: parse_sections(&args.sections)?,
+            exclude: parse_sections(&args.exclude_sections)?,
+            roles: Vec::new(),
+            emphasis: Vec::new(),
+        })
+    }
+
+    fn with_role(mut self, role: &Persona) -> Self {
+        self.roles = role.roles.clone();
+        self.emphasis = role.sections.clone();
+        self
+    }
+
+    /// `--size` wins over `[prompts.<lang>] size`, which wins over full.
+    fn size(&self, language: &str, config: Option<&Config>) -> PromptSize {
+        self.size
+            .or_else(|| config.and_then(|c| c.get_prompt(language)).and_then(|p| p.size))
+            .unwrap_or(PromptSize::Full)
+    }
+
+    fn builder(&self, language: &str, config: Option<&Config>) -> PromptBuilder {
+        let mut builder = PromptBuilder::new()
+            .size(self.size(language, config))
+            .smart(true)
+            .exclude(self.exclude.iter().copied().collect())
+            .emphasize(self.emphasis.iter().copied().collect());
+        if !self.sections.is_empty() {
+            builder = builder.sections(self.sections.iter().copied().collect());
+        }
+        builder
+    }
+
+    /// The flags again, for hooks that call `promptctl show` and regenerate hints.
+    fn to_args(&self) -> String {
+        let join = |sections: &[Section]| {
+            sections.iter().map(Section::name).collect::<Vec<_>>().join(",")
+        };
+        let mut args = String::new();
+        if let Some(size) = self.size {
+            args.push_str(&format!(" --size {}", size.name()));
+        }
+        if !self.sections.is_empty() {
+            args.push_str(&format!(" --section {}", join(&self.sections)));
+        }
+        if !self.exclude.is_empty() {
+            args.push_str(&format!(" --exclude-section {}", join(&self.exclude)));
+        }
+        args
+    }
+}
+
+/// Roles that get a subagent or command: every built-in but the default
+/// developer role, which the main agent already plays, plus project roles.
+/// A project role named or aliased like a built-in takes its place.
+fn prompt_roles(config: Option<&Config>) -> Vec<String> {
+    let mut names: Vec<String> = Role::all()
+        .iter()
+        .filter(|r| **r != Role::Developer)
+        .map(|r| r.name().to_string())
+        .collect();
+    let Some(config) = config else {
+        return names;
+    };
+    for name in config.custom_role_names() {
+        let Some((key, custom)) = config.custom_role(name) else {
+            continue;
+        };
+        let is_this = |n: &str| {
+            n.eq_ignore_ascii_case(key) || custom.aliases.iter().any(|a| a.eq_ignore_ascii_case(n))
+        };
+        if is_this(Role::Developer.name()) {
+            continue;
+        }
+        let first = names.iter().position(|n| is_this(n));
+        names.retain(|n| !is_this(n));
+        names.insert(first.unwrap_or(names.len()), key.to_string());
+    }
+    names
+}
+
+/// Build a prompt per role: its prefix followed by each detected language's
+/// skillset, shaped by the role's sections and addenda.
+fn build_role_prompts(
+    languages: &[String],
+    config: Option<&Config>,
+    index: &ProjectIndex,
+    options: &SkillsetOptions,
+) -> Result<Vec<agents::RolePrompt>, Box<dyn std::error::Error>> {
+    prompt_roles(config)
+        .into_iter()
+        .map(|name| {
+            let role = Persona::resolve(&name, config)?;
+            let options = options.clone().with_role(&role);
+            let mut body = role.prefix.clone();
+            for lang in languages.iter().filter(|l| has_skillset(l, config)) {
+                let skillset = build_skillset(lang, config, index, &options)
+                    .map_err(|e| format!("{name} role: {lang} skillset: {e}"))?;
+                body.push_str(&skillset);
+                body.push_str("\n\n");
+            }
+            let description = if role.description.is_empty() {
+                format!("Works on this project as the {name} role")
+            } else {
+                format!("{} for this project", role.description)
+            };
+            Ok(agents::RolePrompt {
+                name,
+                description,
+                prefix: role.prefix,
+                body,
+                roles: role.roles,
+            })
+        })
+        .collect()
+}
+
+/// Whether `build_skillset` has anything to build for a language, so a
+/// detected language without guidelines is skipped rather than an error.
+fn has_skillset(language: &str, config: Option<&Config>) -> bool {
+    let builtin = prompts::get_builtin_prompt(language, None);
+    structured_skillset(language, config).is_some()
+        || builtin.is_some()
+        || config.is_some_and(|c| {
+            c.resolve_prompt(language, builtin.as_deref()).is_some() || c.language_docs(language).is_some()
+        })
+}
+
+/// Load `.promptctl.toml`, warning about `.promptctl/` docs that no language uses.
+fn load_config() -> Result<Option<Config>, config::ConfigError> {
+    let config = Config::load()?;
+    for path in config.iter().flat_map(|c| &c.ignored_docs) {
+        eprintln!(
+            "{} ignored {} — name it after a language, e.g. .promptctl/rust.md, or add [prompts.<lang>]",
+            "!".yellow().bold(),
+            path.display()
+        );
+    }
+    Ok(config)
+}
+
+/// Scan a project, detecting custom languages declared in config.
+fn scan_project(root: &std::path::Path, config: Option<&Config>) -> ProjectIndex {
+    let custom = config.map(Config::custom_extensions).unwrap_or_default();
+    ProjectIndex::scan_with_extensions(root, custom)
+}
+
 /// Preview hook file paths for dry-run output.
-fn preview_hook_files(agent: Agent, languages: &[String], project_root: &std::path::Path) -> Vec<String> {
+fn preview_hook_files(
+    agent: Agent,
+    languages: &[String],
+    project_root: &std::path::Path,
+    config: Option<&Config>,
+    init: &InitOptions,
+) -> Vec<String> {
+    let rules = config
+        .and_then(|c| c.resolved_rules().ok())
+        .unwrap_or_default();
     let mut files = Vec::new();
     match agent {
         Agent::Claude => {
-            files.push(format!(
-                "{}",
-                project_root.join(".claude/hooks/promptctl-session-start.sh").display()
-            ));
+            if init.skills {
+                for lang in languages {
+                    files.push(format!(
+                        "{}",
+                        project_root
+                            .join(format!(".claude/skills/promptctl-{lang}/SKILL.md"))
+                            .display()
+                    ));
+                }
+            } else {
+                files.push(format!(
+                    "{}",
+                    project_root.join(".claude/hooks/promptctl-session-start.sh").display()
+                ));
+            }
             files.push(format!(
                 "{}",
                 project_root.join(".claude/hooks/promptctl-pre-write.sh").display()
             ));
+            if config.is_some_and(|c| !c.protect.paths.is_empty()) {
+                files.push(format!(
+                    "{}",
+                    project_root.join(".claude/hooks/promptctl-protect.sh").display()
+                ));
+            }
+            if config.is_some_and(|c| c.dependencies.values().any(|p| !p.is_empty())) {
+                files.push(format!(
+                    "{}",
+                    project_root.join(".claude/hooks/promptctl-deps.sh").display()
+                ));
+            }
+            if !rules.is_empty() {
+                files.push(format!(
+                    "{}",
+                    project_root.join(".claude/hooks/promptctl-rules.sh").display()
+                ));
+            }
             files.push(format!(
                 "{}",
                 project_root.join(".claude/settings.json").display()
@@ -457,6 +1330,64 @@ fn preview_hook_files(agent: Agent, languages: &[String], project_root: &std::pa
                         .display()
                 ));
             }
+            for rule in &rules {
+                files.push(format!(
+                    "{}",
+                    project_root
+                        .join(format!(".cursor/rules/promptctl-rule-{}.mdc", rule.slug()))
+                        .display()
+                ));
+            }
+        }
+        Agent::Gemini => {
+            for lang in languages {
+                files.push(format!(
+                    "{}",
+                    project_root.join(format!(".gemini/promptctl-{lang}.md")).display()
+                ));
+            }
+            files.push(format!(
+                "{}",
+                project_root.join(".gemini/settings.json").display()
+            ));
+        }
+        Agent::Cline | Agent::Roo => {
+            let dir = if agent == Agent::Cline { ".clinerules" } else { ".roo/rules" };
+            for lang in languages {
+                files.push(format!(
+                    "{}",
+                    project_root.join(format!("{dir}/promptctl-{lang}.md")).display()
+                ));
+            }
+            if agent == Agent::Roo {
+                for name in prompt_roles(config) {
+                    files.push(format!(
+                        "{}",
+                        project_root
+                            .join(format!(".roo/rules-promptctl-{name}/promptctl.md"))
+                            .display()
+                    ));
+                }
+                files.push(format!("{}", project_root.join(".roomodes").display()));
+            }
+        }
+        Agent::Windsurf => {
+            for lang in languages {
+                files.push(format!(
+                    "{}",
+                    project_root
+                        .join(format!(".windsurf/rules/promptctl-{lang}.md"))
+                        .display()
+                ));
+            }
+            for rule in &rules {
+                files.push(format!(
+                    "{}",
+                    project_root
+                        .join(format!(".windsurf/rules/promptctl-rule-{}.md", rule.slug()))
+                        .display()
+                ));
+            }
         }
         Agent::Copilot => {
             for lang in languages {
@@ -467,6 +1398,34 @@ fn preview_hook_files(agent: Agent, languages: &[String], project_root: &std::pa
                         .display()
                 ));
             }
+            for rule in &rules {
+                files.push(format!(
+                    "{}",
+                    project_root
+                        .join(format!(
+                            ".github/instructions/promptctl-rule-{}.instructions.md",
+                            rule.slug()
+                        ))
+                        .display()
+                ));
+            }
+            for name in prompt_roles(config) {
+                files.push(format!(
+                    "{}",
+                    project_root
+                        .join(format!(".github/chatmodes/promptctl-{name}.chatmode.md"))
+                        .display()
+                ));
+            }
+            if init.vscode_settings {
+                for file in [
+                    ".github/promptctl-commit-messages.md",
+                    ".github/promptctl-review.md",
+                    ".vscode/settings.json",
+                ] {
+                    files.push(format!("{}", project_root.join(file).display()));
+                }
+            }
         }
         _ => {}
     }
@@ -479,7 +1438,7 @@ fn apply_custom_merge(language: &str, config: Option<&Config>, builtin: &str) ->
     let Some(cfg) = config else {
         return builtin.to_string();
     };
-    let Some(custom) = cfg.get_prompt(&lang_lower) else {
+    let Some(custom) = cfg.get_prompt(&lang_lower).filter(|c| !c.keeps_builtin()) else {
         return builtin.to_string();
     };
 
@@ -511,7 +1470,8 @@ fn apply_custom_merge(language: &str, config: Option<&Config>, builtin: &str) ->
     }
 }
 
-/// Build a complete language skillset: structured prompt + custom merge + guardrails.
+/// Build a complete language skillset: structured prompt + custom merge +
+/// project conventions + guardrails.
 ///
 /// This is the self-contained output for `promptctl show <lang>` and agent hooks.
 /// It includes everything an AI needs for a specific language — no overlap with the
@@ -519,43 +1479,240 @@ fn apply_custom_merge(language: &str, config: Option<&Config>, builtin: &str) ->
 fn build_skillset(
     language: &str,
     config: Option<&Config>,
+    index: &ProjectIndex,
+    options: &SkillsetOptions,
 ) -> Result<String, Box<dyn std::error::Error>> {
     let lang_lower = language.to_lowercase();
 
-    // Try structured prompt first (smart + full for maximum skillset coverage)
-    let base = if let Some(structured) = prompts::get_structured_prompt(&lang_lower) {
-        let scan_path = std::env::current_dir().unwrap_or_default();
-        let index = ProjectIndex::scan(&scan_path);
-        let builder = PromptBuilder::new().size(PromptSize::Full).smart(true);
+    let policy = config.and_then(|c| c.dependency_policy(&lang_lower));
 
-        format!(
-            "# {} Development Guidelines\n\n{}",
-            language.to_uppercase(),
-            builder.build(&structured, Some(&index))
-        )
+    // Try structured prompt first (smart filtering within the chosen size tier)
+    let base = if let Some(structured) = structured_skillset(&lang_lower, config) {
+        let builder = options.builder(&lang_lower, config);
+
+        let mut base = format!(
+            "# {}\n\n{}",
+            structured.title,
+            builder.build(&structured, Some(index))
+        );
+        for addenda in options.roles.iter().filter_map(|r| structured.render_addenda(r)) {
+            base.push_str("\n\n");
+            base.push_str(&addenda);
+        }
+        base
     } else {
         // Fall back to raw builtin prompt
-        let builtin = prompts::get_builtin_prompt(&lang_lower);
-
-        if let Some(cfg) = config {
-            if let Some(resolved) = cfg.resolve_prompt(&lang_lower, builtin) {
-                return Ok(resolved);
-            }
+        let version = index.languages.get(&lang_lower).and_then(|l| l.version.as_deref());
+        let builtin = prompts::get_builtin_prompt(&lang_lower, version);
+
+        if let Some(cfg) = config
+            && let Some(resolved) = cfg.resolve_prompt(&lang_lower, builtin.as_deref())
+        {
+            let resolved = with_dependency_policy(resolved, policy);
+            let resolved = with_docs(resolved, &lang_lower, config);
+            let resolved = with_conventions(resolved, &lang_lower, index);
+            return Ok(with_guardrails(resolved, &lang_lower, config));
         }
 
-        builtin
-            .map(|s| s.to_string())
+        let docs_only = config
+            .and_then(|c| c.language_docs(&lang_lower))
+            .map(|_| format!("# {} Development Guidelines", language.to_uppercase()));
+        let raw = builtin
+            .or(docs_only)
             .ok_or_else(|| {
                 format!(
                     "unknown language: '{language}'. Use 'promptctl list' to see available prompts."
                 )
-            })?
+            })?;
+        with_dependency_policy(raw, policy)
     };
 
-    // Apply custom merge if configured
+    // Apply custom merge if configured, then team docs and the project's own style rules
     let merged = apply_custom_merge(&lang_lower, config, &base);
+    let merged = with_docs(merged, &lang_lower, config);
+    let merged = with_conventions(merged, &lang_lower, index);
 
     // Append language-specific guardrails
-    let guardrails = agents::hallucination_guardrails(&lang_lower);
-    Ok(format!("{merged}\n\n{guardrails}"))
+    Ok(with_guardrails(merged, &lang_lower, config))
+}
+
+/// An agent's token budget, which every file `init` generates should fit.
+struct Budget<'a> {
+    limit: usize,
+    counter: &'a dyn TokenCounter,
+    /// Fail instead of shrinking or warning
+    strict: bool,
+}
+
+impl Budget<'_> {
+    /// Token count of `text` when it is over the budget; with `strict`, an
+    /// error listing its sections instead.
+    fn over(&self, what: &str, text: &str) -> Result<Option<usize>, String> {
+        let tokens = self.counter.count(text);
+        if tokens <= self.limit {
+            return Ok(None);
+        }
+        if self.strict {
+            return Err(over_budget_error(what, text, self.limit, self.counter));
+        }
+        Ok(Some(tokens))
+    }
+}
+
+/// A skillset shrunk to fit a token budget.
+struct FittedSkillset {
+    text: String,
+    /// Sections removed to make it fit, in the order they were dropped
+    dropped: Vec<Section>,
+    /// Options that rebuild `text`: the given ones plus the dropped sections
+    options: SkillsetOptions,
+}
+
+/// Build a skillset that fits the budget, dropping its least relevant
+/// sections until it does; sections asked for with `--section` are kept.
+/// Returns `None` when the language has no skillset. With `strict`, an
+/// oversized skillset is an error.
+fn fit_skillset(
+    language: &str,
+    config: Option<&Config>,
+    index: &ProjectIndex,
+    options: &SkillsetOptions,
+    budget: &Budget,
+) -> Result<Option<FittedSkillset>, Box<dyn std::error::Error>> {
+    let Ok(mut text) = build_skillset(language, config, index, options) else {
+        return Ok(None);
+    };
+    let mut shrunk = options.clone();
+    let mut dropped = Vec::new();
+    if budget.over(&format!("{language} skillset"), &text)?.is_some()
+        && let Some(structured) = structured_skillset(language, config)
+    {
+        let emphasis = options.emphasis.iter().copied().collect();
+        let size = options.size(language, config);
+        let order = structured.degrade_order(index, size, &emphasis);
+        for section in order.into_iter().filter(|s| !options.sections.contains(s)) {
+            shrunk.exclude.push(section);
+            let smaller = build_skillset(language, config, index, &shrunk)?;
+            // A replaced prompt keeps its text whatever sections are excluded
+            if smaller == text {
+                shrunk.exclude.pop();
+                continue;
+            }
+            dropped.push(section);
+            text = smaller;
+            if budget.counter.count(&text) <= budget.limit {
+                break;
+            }
+        }
+    }
+    Ok(Some(FittedSkillset {
+        text,
+        dropped,
+        options: shrunk,
+    }))
+}
+
+/// Error for `--strict` listing the size of each section of an oversized file.
+fn over_budget_error(
+    what: &str,
+    text: &str,
+    budget: usize,
+    counter: &dyn TokenCounter,
+) -> String {
+    let mut msg = format!(
+        "{what} is {}, over the ~{budget}-token budget:",
+        token_label(counter.count(text), counter)
+    );
+    let mut breakdown = section_breakdown(text, counter);
+    breakdown.sort_by_key(|(_, tokens)| std::cmp::Reverse(*tokens));
+    for (title, tokens) in breakdown {
+        msg.push_str(&format!("\n  {:>20}  {title}", token_label(tokens, counter)));
+    }
+    msg.push_str("\nUse --size or --exclude-section, or raise the limit in [budgets].");
+    msg
+}
+
+/// A token count with its source: "~N tokens (estimate)" or "N tokens (o200k_base)".
+fn token_label(count: usize, counter: &dyn TokenCounter) -> String {
+    let approx = if counter.name() == tokens::Estimate.name() { "~" } else { "" };
+    format!("{approx}{count} tokens ({})", counter.name())
+}
+
+/// The structured skillset for a language with section overrides and the
+/// dependency policy applied. A project skillset from `.promptctl/<lang>.toml`
+/// takes the place of the built-in.
+fn structured_skillset(language: &str, config: Option<&Config>) -> Option<StructuredPrompt> {
+    let mut structured = config
+        .and_then(|c| c.skillset(language))
+        .cloned()
+        .or_else(|| prompts::get_structured_prompt(language))?;
+
+    if let Some(custom) = config.and_then(|c| c.get_prompt(language)) {
+        apply_section_overrides(&mut structured, custom);
+    }
+    if let Some(policy) = config.and_then(|c| c.dependency_policy(language)) {
+        structured.append_to_section(
+            Section::Dependencies,
+            "Dependencies",
+            &deps::render_policy(policy),
+        );
+    }
+    Some(structured)
+}
+
+/// Append hallucination guardrails, plus any declared in `[prompts.<lang>] guardrails`.
+fn with_guardrails(skillset: String, language: &str, config: Option<&Config>) -> String {
+    let lang_specific = config
+        .and_then(|c| c.skillset(language))
+        .map(|s| s.guardrails.as_str())
+        .or_else(|| prompts::builtin_guardrails(language))
+        .unwrap_or_default();
+    let mut guardrails = agents::hallucination_guardrails(lang_specific);
+    if let Some(extra) = config
+        .and_then(|c| c.get_prompt(language))
+        .and_then(|p| p.guardrails.as_deref())
+    {
+        guardrails.push('\n');
+        guardrails.push_str(extra.trim());
+    }
+    format!("{skillset}\n\n{guardrails}")
+}
+
+/// Apply `[prompts.<lang>.sections.*]` overrides to a structured skillset.
+fn apply_section_overrides(prompt: &mut StructuredPrompt, custom: &config::CustomPrompt) {
+    for (section, o) in custom.section_overrides() {
+        let content = o.content.as_deref().unwrap_or_default();
+        match o.action {
+            SectionAction::Replace => prompt.replace_section(section, o.title.as_deref(), content),
+            SectionAction::Append => {
+                let title = o.title.as_deref().unwrap_or(section.title());
+                prompt.append_to_section(section, title, content);
+            }
+            SectionAction::Disable => prompt.remove_section(section),
+        }
+    }
+}
+
+/// Append the team's `.promptctl/` docs for a language, if any.
+fn with_docs(skillset: String, language: &str, config: Option<&Config>) -> String {
+    match config.and_then(|c| c.language_docs(language)) {
+        Some(docs) => format!("{skillset}\n\n{docs}"),
+        None => skillset,
+    }
+}
+
+/// Append a dependency policy to an unstructured skillset, if one is configured.
+fn with_dependency_policy(skillset: String, policy: Option<&config::DependencyPolicy>) -> String {
+    match policy {
+        Some(policy) => format!("{skillset}\n\n{}", deps::render_policy(policy)),
+        None => skillset,
+    }
+}
+
+/// Append the project's formatter/linter conventions for a language, if any.
+fn with_conventions(skillset: String, language: &str, index: &ProjectIndex) -> String {
+    match conventions::render(&index.conventions, language) {
+        Some(rules) => format!("{skillset}\n\n{rules}"),
+        None => skillset,
+    }
 }
@@ -1,6 +1,7 @@
 //! Prompt builder for creating optimized, context-aware prompts.
 
 use crate::indexer::ProjectIndex;
+use crate::tokens::{self, TokenCounter};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
@@ -43,7 +44,6 @@ impl Section {
         ]
     }
 
-    #[allow(dead_code)]
     pub const fn name(&self) -> &'static str {
         match self {
             Section::Version => "version",
@@ -63,7 +63,26 @@ impl Section {
         }
     }
 
-    #[allow(dead_code)]
+    /// Default heading when a section is added to a prompt that lacks it
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Section::Version => "Version",
+            Section::Style => "Style",
+            Section::ErrorHandling => "Error Handling",
+            Section::Types => "Types",
+            Section::Memory => "Memory & Performance",
+            Section::Concurrency => "Concurrency",
+            Section::Async => "Async",
+            Section::Testing => "Testing",
+            Section::Structure => "Project Structure",
+            Section::Dependencies => "Dependencies",
+            Section::Documentation => "Documentation",
+            Section::Patterns => "Patterns",
+            Section::Tooling => "Tooling",
+            Section::Security => "Security",
+        }
+    }
+
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "version" => Some(Section::Version),
@@ -125,7 +144,6 @@ pub enum PromptSize {
 }
 
 impl PromptSize {
-    #[allow(dead_code)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "minimal" | "min" | "tiny" | "small" => Some(PromptSize::Minimal),
@@ -135,6 +153,15 @@ impl PromptSize {
         }
     }
 
+    /// Approximate token budget that smart filtering fills up to
+    pub const fn token_budget(&self) -> usize {
+        match self {
+            PromptSize::Minimal => 500,
+            PromptSize::Compact => 1500,
+            PromptSize::Full => 3000,
+        }
+    }
+
     pub fn sections(&self) -> HashSet<Section> {
         match self {
             PromptSize::Minimal => Section::minimal_set(),
@@ -143,7 +170,6 @@ impl PromptSize {
         }
     }
 
-    #[allow(dead_code)]
     pub const fn name(&self) -> &'static str {
         match self {
             PromptSize::Minimal => "minimal",
@@ -155,80 +181,247 @@ impl PromptSize {
 
 /// A structured prompt with sections
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct StructuredPrompt {
     pub language: String,
+    /// Top-level heading of the raw form
+    pub title: String,
+    /// Language-specific hallucination guardrails
+    pub guardrails: String,
     pub sections: Vec<PromptSection>,
+    /// Extra content appended only for a given role
+    pub addenda: Vec<RoleAddendum>,
+}
+
+/// Role-specific content of a skillset, e.g. an audit checklist for `security`
+#[derive(Debug, Clone)]
+pub struct RoleAddendum {
+    pub role: String,
+    pub title: String,
+    pub content: String,
 }
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub struct PromptSection {
     pub section: Section,
     pub title: String,
     pub content: String,
     /// Relevance indicators for smart filtering
-    pub relevance_keywords: Vec<&'static str>,
+    pub relevance_keywords: Vec<String>,
+    /// Oldest language version the section applies to
+    pub min_version: Option<String>,
 }
 
 impl StructuredPrompt {
+    /// Append content to a section, creating it (with `title`) if the prompt lacks it.
+    pub fn append_to_section(&mut self, section: Section, title: &str, content: &str) {
+        if let Some(existing) = self.sections.iter_mut().find(|s| s.section == section) {
+            existing.content.push_str("\n\n");
+            existing.content.push_str(content);
+        } else {
+            self.sections.push(PromptSection {
+                section,
+                title: title.to_string(),
+                content: content.to_string(),
+                relevance_keywords: vec![],
+                min_version: None,
+            });
+        }
+    }
+
+    /// Replace a section's content (and optionally its title), adding it if missing.
+    /// Version-specific parts of the section (same id, own `min_version`) go too.
+    pub fn replace_section(&mut self, section: Section, title: Option<&str>, content: &str) {
+        if let Some(i) = self.sections.iter().position(|s| s.section == section) {
+            let existing = &mut self.sections[i];
+            existing.content = content.to_string();
+            existing.min_version = None;
+            if let Some(title) = title {
+                existing.title = title.to_string();
+            }
+            let mut index = 0;
+            self.sections.retain(|s| {
+                index += 1;
+                index - 1 == i || s.section != section
+            });
+        } else {
+            self.sections.push(PromptSection {
+                section,
+                title: title.unwrap_or(section.title()).to_string(),
+                content: content.to_string(),
+                relevance_keywords: vec![],
+                min_version: None,
+            });
+        }
+    }
+
+    /// Drop a section from the prompt.
+    pub fn remove_section(&mut self, section: Section) {
+        self.sections.retain(|s| s.section != section);
+    }
+
+    /// Render the addenda written for a role, if the skillset has any.
+    pub fn render_addenda(&self, role: &str) -> Option<String> {
+        let rendered: Vec<String> = self
+            .addenda
+            .iter()
+            .filter(|a| a.role.eq_ignore_ascii_case(role))
+            .map(|a| format!("## {}\n\n{}", a.title, a.content))
+            .collect();
+        (!rendered.is_empty()).then(|| rendered.join("\n\n"))
+    }
+
+    /// Render every section under the prompt's title, the raw form of a
+    /// skillset. With a language `version`, sections gated on a newer one are
+    /// left out.
+    pub fn render_raw(&self, version: Option<&str>) -> String {
+        let body = self.build_filtered(&Section::full_set(), version);
+        format!("# {}\n\n{body}\n", self.title)
+    }
+
     /// Filter sections by size tier
-    pub fn filter_by_size(&self, size: PromptSize) -> String {
+    pub fn filter_by_size(&self, size: PromptSize, version: Option<&str>) -> String {
         let allowed = size.sections();
-        self.build_with_sections(&allowed)
+        self.build_filtered(&allowed, version)
     }
 
     /// Filter by specific sections
-    pub fn filter_by_sections(&self, sections: &HashSet<Section>) -> String {
-        self.build_with_sections(sections)
+    pub fn filter_by_sections(&self, sections: &HashSet<Section>, version: Option<&str>) -> String {
+        self.build_filtered(sections, version)
+    }
+
+    /// The project's version of this prompt's language, if detected
+    pub fn project_version<'a>(&self, index: &'a ProjectIndex) -> Option<&'a str> {
+        index
+            .languages
+            .get(&self.language)
+            .and_then(|l| l.version.as_deref())
     }
 
     /// Smart filter based on project index
-    pub fn filter_smart(&self, index: &ProjectIndex, base_size: PromptSize) -> String {
-        let mut sections = base_size.sections();
-
-        // Add async section if async frameworks detected
-        let has_async = index.frameworks.iter().any(|f| {
-            matches!(
-                f.name.to_lowercase().as_str(),
-                "tokio" | "async-std" | "actix" | "axum"
-            )
-        });
-        if has_async {
-            sections.insert(Section::Async);
-            sections.insert(Section::Concurrency);
+    pub fn filter_smart(
+        &self,
+        index: &ProjectIndex,
+        base_size: PromptSize,
+        emphasis: &HashSet<Section>,
+    ) -> String {
+        let decisions = self.explain_smart(index, base_size, emphasis);
+        let mut output = String::new();
+        for (section, decision) in self.sections.iter().zip(&decisions) {
+            if decision.included {
+                if !output.is_empty() {
+                    output.push_str("\n\n");
+                }
+                output.push_str(&format!("## {}\n\n", section.title));
+                output.push_str(&section.content);
+            }
         }
+        output
+    }
 
-        // Add testing if tests directory exists
-        if index.structure.has_tests {
-            sections.insert(Section::Testing);
+    /// Decide which sections smart filtering keeps, and why.
+    ///
+    /// Minimal-tier sections and those the role emphasises are always kept, so
+    /// they alone may exceed the budget. The size tier's other sections are
+    /// added next, in document order, while the total stays within the tier's
+    /// token budget. Every remaining section is scored by how many of its
+    /// `relevance_keywords` match the project (dependencies, frameworks, config
+    /// files, extensions, directories) and added in score order under the same
+    /// budget. Sections gated on a newer language version than the project's
+    /// are dropped; when the version is unknown, gated sections are kept.
+    pub fn explain_smart(
+        &self,
+        index: &ProjectIndex,
+        base_size: PromptSize,
+        emphasis: &HashSet<Section>,
+    ) -> Vec<SectionDecision> {
+        let signals = ProjectSignals::from_index(index);
+        let minimal = PromptSize::Minimal.sections();
+        let core = base_size.sections();
+        let budget = base_size.token_budget();
+        let counter = tokens::default_counter();
+        let version = self.project_version(index);
+
+        let mut decisions: Vec<SectionDecision> = self
+            .sections
+            .iter()
+            .map(|s| {
+                let matches = signals.matches(&s.relevance_keywords);
+                SectionDecision {
+                    section: s.section,
+                    title: s.title.clone(),
+                    score: matches.len(),
+                    matches,
+                    tokens: counter.count(&s.title) + counter.count(&s.content),
+                    included: false,
+                    reason: String::new(),
+                }
+            })
+            .collect();
+
+        let mut used = 0;
+        let mut core_candidates = Vec::new();
+        let mut candidates = Vec::new();
+        for (i, (section, decision)) in self.sections.iter().zip(&mut decisions).enumerate() {
+            if let (Some(version), Some(min)) = (version, section.min_version.as_deref())
+                && !version_at_least(version, min)
+            {
+                decision.reason = format!("requires {} {min}, project uses {version}", self.language);
+            } else if minimal.contains(&section.section) && core.contains(&section.section) {
+                decision.included = true;
+                decision.reason = format!("core section for {} size", base_size.name());
+                used += decision.tokens;
+            } else if emphasis.contains(&section.section) {
+                decision.included = true;
+                decision.reason = "emphasised by the role".to_string();
+                used += decision.tokens;
+            } else if core.contains(&section.section) {
+                core_candidates.push(i);
+            } else if decision.score == 0 {
+                decision.reason = "no project signals matched".to_string();
+            } else {
+                candidates.push(i);
+            }
         }
 
-        // Add documentation if docs exist
-        if index.structure.has_docs {
-            sections.insert(Section::Documentation);
+        for i in core_candidates {
+            let decision = &mut decisions[i];
+            if used + decision.tokens <= budget {
+                decision.included = true;
+                decision.reason = format!("core section for {} size", base_size.name());
+                used += decision.tokens;
+            } else {
+                decision.reason = format!(
+                    "core section for {} size, but would exceed the ~{budget}-token budget",
+                    base_size.name()
+                );
+            }
         }
 
-        // Add structure/deps for larger projects
-        if index
-            .languages
-            .values()
-            .map(|l| l.file_count)
-            .sum::<usize>()
-            > 20
-        {
-            sections.insert(Section::Structure);
-            sections.insert(Section::Dependencies);
+        // Highest score first; ties keep document order
+        candidates.sort_by_key(|&i| std::cmp::Reverse(decisions[i].score));
+        for i in candidates {
+            let decision = &mut decisions[i];
+            if used + decision.tokens <= budget {
+                decision.included = true;
+                decision.reason = format!("matched {} project signal(s)", decision.score);
+                used += decision.tokens;
+            } else {
+                decision.reason = format!("would exceed the ~{budget}-token budget");
+            }
         }
 
-        self.build_with_sections(&sections)
+        decisions
     }
 
-    fn build_with_sections(&self, allowed: &HashSet<Section>) -> String {
+    fn build_filtered(&self, allowed: &HashSet<Section>, version: Option<&str>) -> String {
         let mut output = String::new();
 
         for section in &self.sections {
-            if allowed.contains(&section.section) {
+            let too_new = match (version, section.min_version.as_deref()) {
+                (Some(version), Some(min)) => !version_at_least(version, min),
+                _ => false,
+            };
+            if allowed.contains(&section.section) && !too_new {
                 if !output.is_empty() {
                     output.push_str("\n\n");
                 }
@@ -240,12 +433,153 @@ impl StructuredPrompt {
         output
     }
 
-    /// Get estimated token count
-    #[allow(dead_code)]
-    pub fn estimate_tokens(&self, sections: &HashSet<Section>) -> usize {
-        let text = self.build_with_sections(sections);
-        // Rough estimate: ~4 chars per token
-        text.len() / 4
+    /// Order in which to drop sections to shrink the prompt: first those beyond
+    /// the compact tier, then those beyond minimal, least relevant (and latest)
+    /// first within each step. Minimal and emphasised sections are never dropped.
+    pub fn degrade_order(
+        &self,
+        index: &ProjectIndex,
+        size: PromptSize,
+        emphasis: &HashSet<Section>,
+    ) -> Vec<Section> {
+        let compact = PromptSize::Compact.sections();
+        let minimal = PromptSize::Minimal.sections();
+        let decisions = self.explain_smart(index, size, emphasis);
+
+        let mut order: Vec<(usize, &SectionDecision)> = decisions
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| {
+                d.included && !minimal.contains(&d.section) && !emphasis.contains(&d.section)
+            })
+            .collect();
+        order.sort_by_key(|(i, d)| {
+            let tier = if compact.contains(&d.section) { 1 } else { 0 };
+            (tier, d.score, std::cmp::Reverse(*i))
+        });
+        // Excluding a section drops every part that shares its id
+        let mut sections: Vec<Section> = Vec::new();
+        for (_, d) in order {
+            if !sections.contains(&d.section) {
+                sections.push(d.section);
+            }
+        }
+        sections
+    }
+}
+
+/// Split markdown into `## ` sections as (title, text including the heading).
+/// Text before the first heading is titled "(preamble)"; empty parts are skipped.
+pub fn split_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, String)> = vec![("(preamble)".to_string(), String::new())];
+    for line in text.lines() {
+        if let Some(title) = line.strip_prefix("## ") {
+            sections.push((title.trim().to_string(), String::new()));
+        }
+        if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections.retain(|(_, body)| !body.trim().is_empty());
+    sections
+}
+
+/// Split markdown into `## ` sections with their token counts. Text before the
+/// first heading is reported as "(preamble)".
+pub fn section_breakdown(text: &str, counter: &dyn TokenCounter) -> Vec<(String, usize)> {
+    split_sections(text)
+        .into_iter()
+        .map(|(title, body)| (title, counter.count(&body)))
+        .collect()
+}
+
+/// Outcome of smart filtering for one section
+#[derive(Debug, Clone)]
+pub struct SectionDecision {
+    pub section: Section,
+    pub title: String,
+    /// Number of relevance keywords the project matched
+    pub score: usize,
+    /// One description per matched keyword, e.g. "tokio (dependency)"
+    pub matches: Vec<String>,
+    /// Estimated size of the section
+    pub tokens: usize,
+    pub included: bool,
+    pub reason: String,
+}
+
+/// Terms describing a project, each with where it came from.
+struct ProjectSignals {
+    terms: Vec<(String, String)>,
+}
+
+impl ProjectSignals {
+    fn from_index(index: &ProjectIndex) -> Self {
+        let mut terms = Vec::new();
+        let mut add = |text: &str, source: String| {
+            let text = text.to_lowercase();
+            for token in text.split(|c: char| !c.is_ascii_alphanumeric()) {
+                if !token.is_empty() {
+                    terms.push((token.to_string(), source.clone()));
+                }
+            }
+        };
+
+        for dep in &index.dependencies {
+            add(dep, format!("{dep} (dependency)"));
+        }
+        for fw in &index.frameworks {
+            add(&fw.name, format!("{} (framework)", fw.name));
+            for term in fw.category.terms() {
+                add(term, format!("{} ({:?} framework)", fw.name, fw.category));
+            }
+        }
+        for file in &index.config_files {
+            if let Some(name) = file.file_name().and_then(|n| n.to_str()) {
+                add(name, format!("{name} (config file)"));
+            }
+        }
+        for lang in index.languages.values() {
+            for ext in &lang.extensions {
+                add(ext, format!(".{ext} files"));
+            }
+        }
+        if index.structure.has_tests {
+            for term in ["test", "testing", "tests"] {
+                add(term, "tests directory".to_string());
+            }
+        }
+        if index.structure.has_docs {
+            for term in ["doc", "docs", "documentation"] {
+                add(term, "docs directory".to_string());
+            }
+        }
+        if index.structure.has_ci {
+            add("ci", "CI config".to_string());
+        }
+        let file_count: usize = index.languages.values().map(|l| l.file_count).sum();
+        if file_count > 20 {
+            for term in ["structure", "project", "dependency", "dependencies"] {
+                add(term, format!("large project ({file_count} files)"));
+            }
+        }
+
+        Self { terms }
+    }
+
+    /// Describe which keywords the project matches, one entry per keyword.
+    fn matches(&self, keywords: &[String]) -> Vec<String> {
+        keywords
+            .iter()
+            .filter_map(|kw| {
+                let kw = kw.to_lowercase();
+                self.terms
+                    .iter()
+                    .find(|(token, _)| *token == kw || (kw.len() >= 4 && token.starts_with(&kw)))
+                    .map(|(_, source)| format!("{kw} ← {source}"))
+            })
+            .collect()
     }
 }
 
@@ -254,6 +588,8 @@ impl StructuredPrompt {
 pub struct PromptBuilder {
     pub size: PromptSize,
     pub sections: Option<HashSet<Section>>,
+    pub exclude: HashSet<Section>,
+    pub emphasis: HashSet<Section>,
     pub smart_filter: bool,
 }
 
@@ -267,31 +603,98 @@ impl PromptBuilder {
         self
     }
 
-    #[allow(dead_code)]
     pub fn sections(mut self, sections: HashSet<Section>) -> Self {
         self.sections = Some(sections);
         self
     }
 
+    /// Sections to drop whatever the size, selection or smart filter says
+    pub fn exclude(mut self, sections: HashSet<Section>) -> Self {
+        self.exclude = sections;
+        self
+    }
+
+    /// Sections to keep whatever the size tier or smart filter says
+    pub fn emphasize(mut self, sections: HashSet<Section>) -> Self {
+        self.emphasis = sections;
+        self
+    }
+
     pub fn smart(mut self, enabled: bool) -> Self {
         self.smart_filter = enabled;
         self
     }
 
     pub fn build(&self, prompt: &StructuredPrompt, index: Option<&ProjectIndex>) -> String {
+        let pruned;
+        let prompt = if self.exclude.is_empty() {
+            prompt
+        } else {
+            let mut copy = prompt.clone();
+            copy.sections.retain(|s| !self.exclude.contains(&s.section));
+            pruned = copy;
+            &pruned
+        };
+
+        // Every path drops sections gated on a newer language version
+        let version = index.and_then(|idx| prompt.project_version(idx));
+
         // If specific sections provided, use those
         if let Some(ref sections) = self.sections {
-            return prompt.filter_by_sections(sections);
+            return prompt.filter_by_sections(sections, version);
         }
 
         // If smart filtering enabled and we have an index
-        if self.smart_filter {
-            if let Some(idx) = index {
-                return prompt.filter_smart(idx, self.size);
-            }
+        if self.smart_filter
+            && let Some(idx) = index
+        {
+            return prompt.filter_smart(idx, self.size, &self.emphasis);
         }
 
         // Fall back to size-based filtering
-        prompt.filter_by_size(self.size)
+        if self.emphasis.is_empty() {
+            prompt.filter_by_size(self.size, version)
+        } else {
+            let sections = self.size.sections().union(&self.emphasis).copied().collect();
+            prompt.filter_by_sections(&sections, version)
+        }
+    }
+}
+
+/// Compare dotted numeric versions (`1.85.0` >= `1.75`), ignoring anything
+/// that is not a number such as a leading `^` or `v`.
+fn version_at_least(actual: &str, min: &str) -> bool {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches(|c: char| !c.is_ascii_digit())
+            .split('.')
+            .map_while(|p| p.parse().ok())
+            .collect()
+    };
+    let (actual, min) = (parse(actual), parse(min));
+    for i in 0..actual.len().max(min.len()) {
+        let (a, m) = (
+            actual.get(i).copied().unwrap_or(0),
+            min.get(i).copied().unwrap_or(0),
+        );
+        if a != m {
+            return a > m;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert!(version_at_least("1.10", "1.9"));
+        assert!(!version_at_least("1.9", "1.10"));
+        assert!(version_at_least("1.85", "1.85.0"));
+        assert!(version_at_least("1.85.0", "1.85"));
+        assert!(!version_at_least("1.84.1", "1.85"));
+        assert!(version_at_least("go1.22", "1.22"));
+        assert!(version_at_least("1.75.0-nightly", "1.75"));
     }
 }
@@ -1,53 +1,163 @@
-//! Built-in prompt templates for various programming languages.
-
-mod go;
-mod leo;
-mod rust;
-mod solidity;
-mod typescript;
-
-use crate::prompt_builder::StructuredPrompt;
-use std::collections::HashMap;
-
-pub use go::GO_PROMPT;
-pub use leo::LEO_PROMPT;
-pub use rust::RUST_PROMPT;
-pub use solidity::SOLIDITY_PROMPT;
-pub use typescript::TYPESCRIPT_PROMPT;
-
-/// Returns a map of all built-in prompts (raw strings for backward compatibility)
-pub fn builtin_prompts() -> HashMap<&'static str, &'static str> {
-    let mut prompts = HashMap::new();
-    prompts.insert("rust", RUST_PROMPT);
-    prompts.insert("go", GO_PROMPT);
-    prompts.insert("leo", LEO_PROMPT);
-    prompts.insert("typescript", TYPESCRIPT_PROMPT);
-    prompts.insert("ts", TYPESCRIPT_PROMPT);
-    prompts.insert("solidity", SOLIDITY_PROMPT);
-    prompts.insert("sol", SOLIDITY_PROMPT);
-    prompts
-}
-
-/// Get a built-in prompt by language name (raw string)
-pub fn get_builtin_prompt(language: &str) -> Option<&'static str> {
-    builtin_prompts()
-        .get(language.to_lowercase().as_str())
-        .copied()
+//! Built-in skillsets for various programming languages.
+//!
+//! Each language is a TOML file embedded at compile time and parsed into a
+//! [`StructuredPrompt`]; the raw text form is rendered from the same data, so
+//! there is one source of truth per language. Projects can ship their own
+//! skillsets in the same format as `.promptctl/<lang>.toml`.
+//!
+//! ```toml
+//! language = "rust"
+//! title = "Rust Development Guidelines"
+//! aliases = ["rs"]                 # optional
+//! guardrails = "- Do not ..."      # optional, language-specific guardrails
+//!
+//! [[section]]
+//! id = "error-handling"            # a `Section` name
+//! title = "Error Handling"
+//! keywords = ["error", "result"]   # relevance indicators for smart filtering
+//! min_version = "1.75"             # optional, dropped for older projects
+//! content = '''
+//! - Use `?` for propagation
+//! '''
+//!
+//! [[addendum]]                     # optional, only with `--role security`
+//! role = "security"
+//! title = "Audit Checklist"
+//! content = '''
+//! - [ ] Every `unsafe` block has a `// SAFETY:` comment
+//! '''
+//! ```
+
+use crate::prompt_builder::{PromptSection, RoleAddendum, Section, StructuredPrompt};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const BUILTIN_SOURCES: &[(&str, &str)] = &[
+    ("rust.toml", include_str!("rust.toml")),
+    ("go.toml", include_str!("go.toml")),
+    ("leo.toml", include_str!("leo.toml")),
+    ("typescript.toml", include_str!("typescript.toml")),
+    ("solidity.toml", include_str!("solidity.toml")),
+];
+
+/// On-disk skillset format
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SkillsetFile {
+    pub language: String,
+    pub title: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub guardrails: String,
+    #[serde(default, rename = "section")]
+    pub sections: Vec<SectionDef>,
+    #[serde(default, rename = "addendum")]
+    pub addenda: Vec<AddendumDef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SectionDef {
+    pub id: Section,
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub min_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddendumDef {
+    pub role: String,
+    pub title: String,
+    pub content: String,
+}
+
+impl SkillsetFile {
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Language name and aliases, lowercased, for lookups.
+    pub fn names(&self) -> Vec<String> {
+        std::iter::once(&self.language)
+            .chain(&self.aliases)
+            .map(|n| n.to_lowercase())
+            .collect()
+    }
+
+    pub fn into_prompt(self) -> StructuredPrompt {
+        StructuredPrompt {
+            language: self.language.to_lowercase(),
+            title: self.title,
+            guardrails: self.guardrails.trim().to_string(),
+            sections: self
+                .sections
+                .into_iter()
+                .map(|s| PromptSection {
+                    section: s.id,
+                    title: s.title,
+                    content: s.content.trim().to_string(),
+                    relevance_keywords: s.keywords,
+                    min_version: s.min_version,
+                })
+                .collect(),
+            addenda: self
+                .addenda
+                .into_iter()
+                .map(|a| RoleAddendum {
+                    role: a.role.to_lowercase(),
+                    title: a.title,
+                    content: a.content.trim().to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Built-in skillsets with their lookup names, parsed once.
+fn builtins() -> &'static [(Vec<String>, StructuredPrompt)] {
+    static BUILTINS: OnceLock<Vec<(Vec<String>, StructuredPrompt)>> = OnceLock::new();
+    BUILTINS.get_or_init(|| {
+        BUILTIN_SOURCES
+            .iter()
+            .map(|(file, text)| {
+                let skillset = SkillsetFile::parse(text)
+                    .unwrap_or_else(|e| panic!("built-in skillset {file} is invalid: {e}"));
+                (skillset.names(), skillset.into_prompt())
+            })
+            .collect()
+    })
+}
+
+fn find_builtin(language: &str) -> Option<&'static StructuredPrompt> {
+    let language = language.to_lowercase();
+    builtins()
+        .iter()
+        .find(|(names, _)| names.contains(&language))
+        .map(|(_, prompt)| prompt)
+}
+
+/// Get a built-in prompt by language name (raw string), without the sections
+/// gated on a newer `version` of the language than the project's
+pub fn get_builtin_prompt(language: &str, version: Option<&str>) -> Option<String> {
+    find_builtin(language).map(|prompt| prompt.render_raw(version))
 }
 
 /// Get a structured prompt for a language
 pub fn get_structured_prompt(language: &str) -> Option<StructuredPrompt> {
-    match language.to_lowercase().as_str() {
-        "rust" => Some(rust::structured_prompt()),
-        "go" => Some(go::structured_prompt()),
-        "leo" => Some(leo::structured_prompt()),
-        "typescript" | "ts" => Some(typescript::structured_prompt()),
-        "solidity" | "sol" => Some(solidity::structured_prompt()),
-        _ => None,
-    }
+    find_builtin(language).cloned()
+}
+
+/// Language-specific hallucination guardrails of a built-in skillset
+pub fn builtin_guardrails(language: &str) -> Option<&'static str> {
+    find_builtin(language).map(|p| p.guardrails.as_str())
 }
 
 /// List all available built-in language names
 pub fn available_languages() -> Vec<&'static str> {
-    vec!["rust", "go", "leo", "typescript", "solidity"]
+    builtins().iter().map(|(_, p)| p.language.as_str()).collect()
 }
@@ -1,661 +0,0 @@
-//! Solidity smart contract prompt template.
-//!
-//! Gold Standard: adversarial mindset, Safety > Gas, CEI pattern,
-//! Foundry-first tooling, explicit vulnerability checks.
-
-use crate::prompt_builder::{PromptSection, Section, StructuredPrompt};
-
-pub const SOLIDITY_PROMPT: &str = r#"# Solidity Development Guidelines (0.8.33)
-
-> **Adversarial Mindset:** Assume every external caller is a contract designed to exploit you.
-> **Safety > Gas. Always.** Never sacrifice correctness or security for gas savings.
-
-## Language Version
-- Target **Solidity 0.8.33** (latest stable)
-- Use `pragma solidity ^0.8.33;` for production contracts
-- Enable optimizer with 200+ runs for deployed contracts
-- Use `via-ir` pipeline for complex contracts
-- Require explicit `override` on all inherited functions
-
-## Contract Structure
-
-### Layout Convention
-```solidity
-// SPDX-License-Identifier: MIT
-pragma solidity ^0.8.33;
-
-import {ERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
-import {Ownable} from "@openzeppelin/contracts/access/Ownable.sol";
-import {ReentrancyGuard} from "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
-
-/// @title Token Contract
-/// @author Your Name
-/// @notice Brief description
-/// @dev Implementation details
-contract MyToken is ERC20, Ownable, ReentrancyGuard {
-    // Type declarations
-    struct User { address addr; uint256 balance; }
-    enum Status { Active, Paused, Stopped }
-
-    // State variables (storage layout order matters!)
-    uint256 public constant MAX_SUPPLY = 1_000_000e18;
-    uint256 public immutable deployedAt;
-    uint256 private _totalMinted;
-    mapping(address => User) private _users;
-
-    // Events
-    event Minted(address indexed to, uint256 amount);
-
-    // Errors (always prefer custom errors — gas efficient)
-    error InsufficientBalance(uint256 available, uint256 required);
-    error Unauthorized(address caller);
-    error ZeroAddress();
-
-    // Modifiers
-    modifier onlyActive() {
-        require(status == Status.Active, "Not active");
-        _;
-    }
-
-    // Constructor
-    constructor() ERC20("MyToken", "MTK") Ownable(msg.sender) {
-        deployedAt = block.timestamp;
-    }
-
-    // External functions (nonReentrant on state-changing)
-    // Public functions
-    // Internal functions
-    // Private functions
-}
-```
-
-## Type System
-
-### Value Types
-```solidity
-// Fixed-size types
-uint256 amount;      // 256-bit unsigned (always use uint256 explicitly)
-int128 delta;        // 128-bit signed
-address owner;       // 20 bytes — NEVER trust msg.sender blindly
-address payable recipient;  // Can receive ETH
-bool active;         // true/false
-bytes32 hash;        // Fixed-size byte array
-
-// User-defined value types (0.8.8+)
-type TokenId is uint256;
-type Price is uint128;
-
-function process(TokenId id) external {
-    uint256 raw = TokenId.unwrap(id);
-    TokenId newId = TokenId.wrap(raw + 1);
-}
-```
-
-### Reference Types
-```solidity
-// Arrays
-uint256[] dynamicArray;
-uint256[10] fixedArray;
-
-// Mappings (cannot iterate, no length, no deletion)
-mapping(address => uint256) balances;
-mapping(address => mapping(address => uint256)) allowances;
-
-// Structs
-struct Order {
-    address maker;
-    uint256 amount;
-    uint256 price;
-}
-```
-
-## Error Handling
-
-### Custom Errors (always preferred)
-```solidity
-// Gas efficient: ~100 gas vs ~2000 for require with string
-error InsufficientBalance(uint256 available, uint256 required);
-error Unauthorized();
-error InvalidInput(string reason);
-error ZeroAddress();
-
-function withdraw(uint256 amount) external {
-    if (msg.sender == address(0)) revert ZeroAddress();
-    uint256 balance = balances[msg.sender];
-    if (balance < amount) {
-        revert InsufficientBalance(balance, amount);
-    }
-    // ...
-}
-```
-
-### Require/Assert/Revert
-```solidity
-// require: Input validation and conditions
-require(amount > 0, "Amount must be positive");
-require(msg.sender == owner, "Not owner");
-
-// assert: Invariants that should NEVER fail (bug detection)
-assert(totalSupply == sumOfBalances);
-
-// revert: Complex conditions with custom errors
-if (block.timestamp < unlockTime) {
-    revert TooEarly(block.timestamp, unlockTime);
-}
-
-// try/catch for external calls — DO NOT trust external return values
-try externalContract.call() returns (uint256 result) {
-    // Validate result before using
-} catch Error(string memory reason) {
-    // require/revert with string
-} catch (bytes memory lowLevelData) {
-    // Custom error or panic
-}
-```
-
-## Security Patterns
-
-> **Rule #1:** Assume the caller is a contract looking to exploit you.
-> **Rule #2:** Safety > Gas. Always.
-> **Rule #3:** Every external call is a potential attack vector.
-
-### Reentrancy Protection (MANDATORY)
-```solidity
-import {ReentrancyGuard} from "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
-
-contract Vault is ReentrancyGuard {
-    mapping(address => uint256) private _balances;
-
-    // ALWAYS use Checks-Effects-Interactions (CEI) pattern
-    function withdraw(uint256 amount) external nonReentrant {
-        // 1. CHECKS — validate all conditions
-        uint256 balance = _balances[msg.sender];
-        if (balance < amount) revert InsufficientBalance(balance, amount);
-
-        // 2. EFFECTS — update state BEFORE any external call
-        _balances[msg.sender] = balance - amount;
-
-        // 3. INTERACTIONS — external calls LAST, always
-        (bool success, ) = msg.sender.call{value: amount}("");
-        require(success, "Transfer failed");
-
-        // NEVER update state after an external call
-    }
-}
-```
-
-### Access Control (EVERY public/external function needs explicit authorization)
-```solidity
-import {AccessControl} from "@openzeppelin/contracts/access/AccessControl.sol";
-
-contract MyContract is AccessControl {
-    bytes32 public constant ADMIN_ROLE = keccak256("ADMIN_ROLE");
-    bytes32 public constant MINTER_ROLE = keccak256("MINTER_ROLE");
-
-    constructor() {
-        _grantRole(DEFAULT_ADMIN_ROLE, msg.sender);
-        _grantRole(ADMIN_ROLE, msg.sender);
-    }
-
-    // Missing modifiers = critical vulnerability
-    function mint(address to, uint256 amount) external onlyRole(MINTER_ROLE) {
-        if (to == address(0)) revert ZeroAddress();
-        _mint(to, amount);
-    }
-}
-```
-
-### Unchecked Math (use with extreme caution)
-```solidity
-// 0.8.0+ has built-in overflow checks — do NOT disable unless provably safe
-uint256 a = type(uint256).max;
-uint256 b = a + 1;  // Reverts automatically
-
-// unchecked: ONLY when overflow is mathematically impossible
-// Document WHY it's safe in a comment
-function incrementCounter() external {
-    unchecked {
-        // Safe: counter < type(uint256).max is guaranteed by MAX_COUNT check above
-        counter++;
-    }
-}
-```
-
-### Additional Security Checklist
-- **Front-running:** Use commit-reveal or deadline parameters for price-sensitive operations
-- **Flash loans:** Validate state consistency within the same transaction
-- **Signature replay:** Include chain ID + nonce + deadline in signed messages
-- **Denial of Service:** Avoid unbounded loops over user-controlled arrays
-- **Oracle manipulation:** Use TWAP or multiple oracle sources, never spot prices
-- **tx.origin:** NEVER use `tx.origin` for authorization — use `msg.sender`
-- **delegatecall:** Extremely dangerous — storage layout must match exactly
-- **selfdestruct:** Deprecated since 0.8.24; force-sent ETH bypasses receive/fallback
-
-## Gas Optimization
-
-> Remember: **Safety > Gas. Always.** Only optimize gas after correctness is proven.
-
-### Storage Patterns
-```solidity
-// Pack variables (32-byte slots)
-// Bad: 3 slots
-uint256 a;    // slot 0
-uint128 b;    // slot 1
-uint128 c;    // slot 2
-
-// Good: 2 slots
-uint256 a;    // slot 0
-uint128 b;    // slot 1 (first half)
-uint128 c;    // slot 1 (second half)
-
-// Use immutable for constructor-set values
-uint256 public immutable deployTime;
-
-// Use constant for compile-time constants
-uint256 public constant FEE_DENOMINATOR = 10000;
-
-// Transient storage (0.8.24+) — cleared after transaction
-contract Lock {
-    bytes32 constant LOCK_SLOT = keccak256("lock");
-
-    modifier nonReentrantTransient() {
-        assembly {
-            if tload(LOCK_SLOT) { revert(0, 0) }
-            tstore(LOCK_SLOT, 1)
-        }
-        _;
-        assembly {
-            tstore(LOCK_SLOT, 0)
-        }
-    }
-}
-```
-
-### Calldata vs Memory
-```solidity
-// Use calldata for read-only external function params
-function process(bytes calldata data) external pure returns (bytes32) {
-    return keccak256(data);  // No copy needed
-}
-
-// Use memory when modification needed
-function modify(uint256[] memory arr) internal pure {
-    arr[0] = 100;
-}
-```
-
-## Events & Logging
-
-```solidity
-// Index up to 3 parameters for filtering
-event Transfer(
-    address indexed from,
-    address indexed to,
-    uint256 value  // Not indexed, in data
-);
-
-// Anonymous events save gas but lose signature
-event AnonymousLog(uint256 value) anonymous;
-
-function transfer(address to, uint256 amount) external {
-    // ...
-    emit Transfer(msg.sender, to, amount);
-}
-```
-
-## Testing
-
-> Generate all test output compatible with **Foundry/Forge** (`forge test`).
-
-```solidity
-// Foundry test
-import {Test, console} from "forge-std/Test.sol";
-
-contract TokenTest is Test {
-    Token token;
-    address alice = makeAddr("alice");
-    address bob = makeAddr("bob");
-
-    function setUp() public {
-        token = new Token();
-        deal(address(token), alice, 1000e18);
-    }
-
-    function test_Transfer() public {
-        vm.prank(alice);
-        token.transfer(bob, 100e18);
-        assertEq(token.balanceOf(bob), 100e18);
-    }
-
-    function testFuzz_Transfer(uint256 amount) public {
-        amount = bound(amount, 0, token.balanceOf(alice));
-        vm.prank(alice);
-        token.transfer(bob, amount);
-        assertEq(token.balanceOf(bob), amount);
-    }
-
-    function testRevert_InsufficientBalance() public {
-        vm.prank(alice);
-        vm.expectRevert(abi.encodeWithSelector(
-            Token.InsufficientBalance.selector, 1000e18, 2000e18
-        ));
-        token.transfer(bob, 2000e18);
-    }
-
-    // ALWAYS test reentrancy attacks explicitly
-    function test_ReentrancyAttack() public {
-        Attacker attacker = new Attacker(address(token));
-        deal(address(token), address(attacker), 100e18);
-        vm.expectRevert();
-        attacker.attack();
-    }
-
-    // ALWAYS test access control
-    function testRevert_UnauthorizedMint() public {
-        vm.prank(alice);  // alice has no MINTER_ROLE
-        vm.expectRevert();
-        token.mint(alice, 1000e18);
-    }
-}
-```
-
-## CLI & Tooling
-
-```bash
-# Foundry (recommended — all output must be compatible)
-forge init my_project
-forge build
-forge test -vvv
-forge coverage
-forge snapshot                # Gas benchmarks
-forge script script/Deploy.s.sol --rpc-url $RPC_URL --broadcast
-forge verify-contract <addr> src/Token.sol:Token --etherscan-api-key $KEY
-
-# Static analysis (run before every audit)
-slither .                     # Detects common vulnerabilities
-mythril analyze src/Token.sol # Symbolic execution
-
-# Hardhat alternative
-npx hardhat compile
-npx hardhat test
-npx hardhat run scripts/deploy.js --network mainnet
-```
-"#;
-
-/// Create a structured Solidity prompt with sections
-pub fn structured_prompt() -> StructuredPrompt {
-    StructuredPrompt {
-        language: "solidity".to_string(),
-        sections: vec![
-            PromptSection {
-                section: Section::Version,
-                title: "Language Version".to_string(),
-                content: r#"- Target **Solidity 0.8.33** (latest stable)
-- Use `pragma solidity ^0.8.33;` for production contracts
-- Enable optimizer with 200+ runs for deployed contracts
-- Use `via-ir` pipeline for complex contracts
-- Require explicit `override` on all inherited functions"#
-                    .to_string(),
-                relevance_keywords: vec!["solidity", "version", "pragma", "evm"],
-            },
-            PromptSection {
-                section: Section::Structure,
-                title: "Contract Structure".to_string(),
-                content: r#"Layout order: License → Pragma → Imports → Interfaces → Libraries → Contracts
-
-Within contract: Type declarations → State variables → Events → Errors → Modifiers → Constructor → Functions (external → public → internal → private)
-
-```solidity
-// SPDX-License-Identifier: MIT
-pragma solidity ^0.8.33;
-
-import {ReentrancyGuard} from "@openzeppelin/contracts/utils/ReentrancyGuard.sol";
-
-contract MyContract is ReentrancyGuard {
-    uint256 public constant MAX = 100;
-    uint256 public immutable deployedAt;
-    uint256 private _value;
-
-    event ValueChanged(uint256 newValue);
-    error InvalidValue(uint256 value);
-    error ZeroAddress();
-
-    constructor() { deployedAt = block.timestamp; }
-}
-```"#
-                    .to_string(),
-                relevance_keywords: vec!["contract", "structure", "layout", "import"],
-            },
-            PromptSection {
-                section: Section::Types,
-                title: "Type System".to_string(),
-                content: r#"**Value Types:** `uint256`, `int256`, `address`, `address payable`, `bool`, `bytes32`
-**User-Defined Value Types:** `type TokenId is uint256;`
-**Reference Types:** `mapping`, arrays, structs
-
-```solidity
-type Price is uint128;
-mapping(address => uint256) balances;
-struct Order { address maker; uint256 amount; }
-```
-
-Prefer `uint256` over smaller types in function parameters (EVM operates on 256-bit words)."#
-                    .to_string(),
-                relevance_keywords: vec!["type", "mapping", "struct", "uint", "address"],
-            },
-            PromptSection {
-                section: Section::ErrorHandling,
-                title: "Error Handling".to_string(),
-                content: r#"**Always use custom errors** (gas efficient, ~100 gas vs ~2000 for require strings):
-```solidity
-error InsufficientBalance(uint256 available, uint256 required);
-error Unauthorized();
-error ZeroAddress();
-
-function withdraw(uint256 amount) external {
-    if (msg.sender == address(0)) revert ZeroAddress();
-    uint256 balance = balances[msg.sender];
-    if (balance < amount) {
-        revert InsufficientBalance(balance, amount);
-    }
-}
-```
-
-Use `require` for input validation, `assert` for invariants (bug detection), try/catch for external calls.
-**Never trust return values from external calls without validation.**"#
-                    .to_string(),
-                relevance_keywords: vec!["error", "require", "revert", "assert", "catch"],
-            },
-            PromptSection {
-                section: Section::Security,
-                title: "Security Patterns".to_string(),
-                content: r#"> **Adversarial Mindset:** Assume every external caller is a contract designed to exploit you.
-> **Safety > Gas. Always.** Never sacrifice correctness for gas savings.
-> **Every external call is a potential attack vector.**
-
-**Reentrancy (MANDATORY — CEI pattern):**
-```solidity
-function withdraw(uint256 amount) external nonReentrant {
-    // 1. CHECKS — validate all conditions
-    uint256 balance = _balances[msg.sender];
-    if (balance < amount) revert InsufficientBalance(balance, amount);
-    // 2. EFFECTS — update state BEFORE external calls
-    _balances[msg.sender] = balance - amount;
-    // 3. INTERACTIONS — external calls LAST, always
-    (bool success,) = msg.sender.call{value: amount}("");
-    require(success);
-    // NEVER update state after an external call
-}
-```
-
-**Access Control:** Every public/external state-changing function MUST have explicit authorization. Missing modifiers = critical vulnerability.
-**Unchecked Math:** Built-in since 0.8.0; use `unchecked` ONLY when overflow is mathematically provable impossible, and document WHY.
-**Front-running:** Use commit-reveal or deadline parameters for price-sensitive ops.
-**tx.origin:** NEVER use for authorization — use `msg.sender`.
-**delegatecall:** Extremely dangerous — storage layout must match exactly.
-**selfdestruct:** Deprecated since 0.8.24; force-sent ETH bypasses receive/fallback."#
-                    .to_string(),
-                relevance_keywords: vec![
-                    "security",
-                    "reentrancy",
-                    "access",
-                    "overflow",
-                    "audit",
-                    "exploit",
-                    "CEI",
-                    "vulnerability",
-                    "attack",
-                    "frontrun",
-                ],
-            },
-            PromptSection {
-                section: Section::Memory,
-                title: "Gas Optimization".to_string(),
-                content: r#"> **Safety > Gas. Always.** Only optimize after correctness is proven.
-
-- Pack storage variables (32-byte slots)
-- Use `immutable` for constructor-set values, `constant` for compile-time
-- Use `calldata` for read-only external params
-- Use `unchecked` only when mathematically provable safe (document WHY)
-- Use transient storage (0.8.24+) for reentrancy locks
-
-```solidity
-// Bad: 3 slots          // Good: 2 slots
-uint256 a;               uint256 a;
-uint128 b;               uint128 b;
-uint128 c;               uint128 c;  // Same slot as b
-```"#
-                    .to_string(),
-                relevance_keywords: vec![
-                    "gas",
-                    "optimization",
-                    "storage",
-                    "memory",
-                    "calldata",
-                ],
-            },
-            PromptSection {
-                section: Section::Testing,
-                title: "Testing".to_string(),
-                content: r#"> Generate all test output compatible with **Foundry/Forge** (`forge test`).
-
-```solidity
-import {Test} from "forge-std/Test.sol";
-
-contract TokenTest is Test {
-    Token token;
-    address alice = makeAddr("alice");
-
-    function setUp() public {
-        token = new Token();
-        deal(address(token), alice, 1000e18);
-    }
-
-    function test_Transfer() public {
-        vm.prank(alice);
-        token.transfer(bob, 100e18);
-        assertEq(token.balanceOf(bob), 100e18);
-    }
-
-    function testFuzz_Transfer(uint256 amount) public {
-        amount = bound(amount, 0, 1000e18);
-        // ...
-    }
-
-    // ALWAYS test reentrancy attacks explicitly
-    function test_ReentrancyAttack() public {
-        Attacker attacker = new Attacker(address(token));
-        vm.expectRevert();
-        attacker.attack();
-    }
-
-    // ALWAYS test access control
-    function testRevert_UnauthorizedMint() public {
-        vm.prank(alice);
-        vm.expectRevert();
-        token.mint(alice, 1000e18);
-    }
-}
-```"#
-                    .to_string(),
-                relevance_keywords: vec![
-                    "test",
-                    "foundry",
-                    "forge",
-                    "fuzz",
-                    "assert",
-                    "reentrancy",
-                    "attack",
-                ],
-            },
-            PromptSection {
-                section: Section::Tooling,
-                title: "Tooling".to_string(),
-                content: r#"```bash
-# Foundry (recommended — all output must be Foundry-compatible)
-forge init my_project
-forge build
-forge test -vvv
-forge coverage
-forge snapshot                # Gas benchmarks
-forge script script/Deploy.s.sol --rpc-url $RPC --broadcast
-
-# Static analysis (run before every audit)
-slither .
-mythril analyze src/Token.sol
-```"#
-                    .to_string(),
-                relevance_keywords: vec![
-                    "foundry",
-                    "hardhat",
-                    "forge",
-                    "deploy",
-                    "compile",
-                    "slither",
-                    "mythril",
-                ],
-            },
-            PromptSection {
-                section: Section::Dependencies,
-                title: "Dependencies & Frameworks".to_string(),
-                content: r#"- **OpenZeppelin Contracts** (v5.x) — battle-tested base contracts, always prefer over hand-rolled
-- **Foundry** — primary build/test/deploy toolchain; all generated code must pass `forge test`
-- **Slither** + **Mythril** — static analysis before any audit or deployment
-- Prefer `forge install` over npm for Solidity dependencies
-- Pin dependency versions in `foundry.toml` remappings
-- Never hand-roll access control, reentrancy guards, or ERC implementations — use OpenZeppelin"#
-                    .to_string(),
-                relevance_keywords: vec![
-                    "openzeppelin",
-                    "foundry",
-                    "dependency",
-                    "framework",
-                    "library",
-                    "import",
-                ],
-            },
-            PromptSection {
-                section: Section::Patterns,
-                title: "Common Patterns".to_string(),
-                content: r#"```solidity
-// ERC20 Token
-import {ERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
-contract MyToken is ERC20 {
-    constructor() ERC20("Name", "SYM") { _mint(msg.sender, 1000000e18); }
-}
-
-// Proxy/Upgradeable (audit storage layout carefully)
-import {UUPSUpgradeable} from "@openzeppelin/contracts-upgradeable/proxy/utils/UUPSUpgradeable.sol";
-
-// Factory pattern
-function createPair(address t0, address t1) external returns (address pair) {
-    if (t0 == address(0) || t1 == address(0)) revert ZeroAddress();
-    pair = address(new Pair(t0, t1));
-}
-```"#
-                    .to_string(),
-                relevance_keywords: vec!["pattern", "erc20", "proxy", "factory", "upgradeable"],
-            },
-        ],
-    }
-}
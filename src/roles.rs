@@ -1,5 +1,7 @@
 //! Role definitions for coding personas.
 
+use crate::config::Config;
+use crate::prompt_builder::Section;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -70,6 +72,16 @@ impl Role {
         }
     }
 
+    /// Skillset sections the role always keeps, whatever the size tier
+    pub const fn priority_sections(&self) -> &'static [Section] {
+        match self {
+            Role::Security => &[Section::Security, Section::ErrorHandling],
+            Role::Performance => &[Section::Memory, Section::Concurrency],
+            Role::Documentation => &[Section::Documentation],
+            _ => &[],
+        }
+    }
+
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "developer" | "dev" => Some(Role::Developer),
@@ -91,6 +103,221 @@ impl fmt::Display for Role {
     }
 }
 
+/// A built-in, project or composite role, resolved for prompt building.
+#[derive(Debug, Clone)]
+pub struct Persona {
+    /// Canonical name; composites join their parts with `+`
+    pub name: String,
+    pub description: String,
+    pub prefix: String,
+    /// Skillset sections this role keeps whatever the size tier
+    pub sections: Vec<Section>,
+    /// Names of the single roles this one is made of, for skillset addenda
+    pub roles: Vec<String>,
+}
+
+impl Persona {
+    pub fn builtin(role: Role) -> Self {
+        Self {
+            name: role.name().to_string(),
+            description: role.description().to_string(),
+            prefix: role.prompt_prefix().to_string(),
+            sections: role.priority_sections().to_vec(),
+            roles: vec![role.name().to_string()],
+        }
+    }
+
+    /// Resolve a role name: `[roles.<name>]` (or one of its aliases) wins over
+    /// a built-in of the same name. Names joined with `+` or `,` (e.g.
+    /// `senior+security`) combine several roles into one.
+    pub fn resolve(name: &str, config: Option<&Config>) -> Result<Self, String> {
+        Self::resolve_inner(name, config, &mut Vec::new())
+    }
+
+    fn resolve_inner(
+        name: &str,
+        config: Option<&Config>,
+        seen: &mut Vec<String>,
+    ) -> Result<Self, String> {
+        let parts: Vec<&str> = name
+            .split(['+', ','])
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+        if parts.len() > 1 {
+            let personas = parts
+                .iter()
+                .map(|p| Self::resolve_inner(p, config, seen))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(Self::combine(personas));
+        }
+
+        if let Some((name, custom)) = config.and_then(|c| c.custom_role(name)) {
+            if seen.iter().any(|s| s == name) {
+                return Err(format!("role '{name}' combines itself"));
+            }
+            seen.push(name.to_string());
+            let mut parts = custom
+                .combine
+                .iter()
+                .map(|p| Self::resolve_inner(p, config, seen))
+                .collect::<Result<Vec<_>, _>>()?;
+            seen.pop();
+
+            if let Some(prefix) = custom.prefix.as_deref().map(str::trim) {
+                let prefix = if prefix.starts_with("## ") {
+                    format!("{prefix}\n\n")
+                } else {
+                    format!("## Role: {name}\n\n{prefix}\n\n")
+                };
+                parts.push(Self {
+                    name: name.to_string(),
+                    description: custom.description.clone(),
+                    prefix,
+                    sections: custom.sections.clone(),
+                    roles: vec![name.to_string()],
+                });
+            }
+            let mut persona = Self::combine(parts);
+            persona.name = name.to_string();
+            if !custom.description.is_empty() {
+                persona.description = custom.description.clone();
+            }
+            persona.roles.push(name.to_string());
+            persona.roles.dedup();
+            for section in &custom.sections {
+                if !persona.sections.contains(section) {
+                    persona.sections.push(*section);
+                }
+            }
+            return Ok(persona);
+        }
+
+        Role::from_str(name).map(Self::builtin).ok_or_else(|| {
+            let mut available: Vec<&str> = Role::all().iter().map(Role::name).collect();
+            if let Some(config) = config {
+                available.extend(config.custom_role_names());
+            }
+            format!("unknown role: '{name}'. Available: {}", available.join(", "))
+        })
+    }
+
+    /// Merge roles into one: prefixes under a single heading, sections and
+    /// role names without duplicates. A role named twice is merged once.
+    fn combine(parts: Vec<Persona>) -> Self {
+        let mut personas: Vec<Persona> = Vec::new();
+        for part in parts {
+            if !personas.iter().any(|p| p.name == part.name) {
+                personas.push(part);
+            }
+        }
+        if personas.len() == 1 {
+            return personas.into_iter().next().expect("one persona");
+        }
+        let mut combined = Self {
+            name: personas.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join("+"),
+            description: personas
+                .iter()
+                .map(|p| p.description.as_str())
+                .filter(|d| !d.is_empty())
+                .collect::<Vec<_>>()
+                .join("; "),
+            prefix: merge_prefixes(&personas.iter().map(|p| p.prefix.as_str()).collect::<Vec<_>>()),
+            sections: Vec::new(),
+            roles: Vec::new(),
+        };
+        for persona in personas {
+            for section in persona.sections {
+                if !combined.sections.contains(&section) {
+                    combined.sections.push(section);
+                }
+            }
+            for role in persona.roles {
+                if !combined.roles.contains(&role) {
+                    combined.roles.push(role);
+                }
+            }
+        }
+        combined
+    }
+}
+
+/// Merge role prefixes: one `## Role:` heading naming every role, each role's
+/// introduction, then every `###` list once, with the items of all roles that
+/// share it. Repeated items are dropped and numbered lists renumbered.
+fn merge_prefixes(prefixes: &[&str]) -> String {
+    let mut titles = Vec::new();
+    let mut intros = Vec::new();
+    let mut lists: Vec<(String, Vec<String>)> = Vec::new();
+
+    for prefix in prefixes {
+        let mut current: Option<usize> = None;
+        let mut intro = Vec::new();
+        for line in prefix.lines() {
+            let trimmed = line.trim();
+            if let Some(title) = trimmed.strip_prefix("## ") {
+                titles.push(title.trim_start_matches("Role:").trim().to_string());
+            } else if let Some(heading) = trimmed.strip_prefix("### ") {
+                let heading = heading.trim().to_string();
+                current = Some(match lists.iter().position(|(h, _)| *h == heading) {
+                    Some(i) => i,
+                    None => {
+                        lists.push((heading, Vec::new()));
+                        lists.len() - 1
+                    }
+                });
+            } else if trimmed.is_empty() {
+                continue;
+            } else if let Some(i) = current {
+                let item = list_item_text(trimmed);
+                if !lists[i].1.iter().any(|existing| list_item_text(existing) == item) {
+                    lists[i].1.push(trimmed.to_string());
+                }
+            } else {
+                intro.push(trimmed);
+            }
+        }
+        if !intro.is_empty() {
+            intros.push(intro.join("\n"));
+        }
+    }
+
+    let mut output = format!("## Role: {}\n\n", titles.join(" + "));
+    for intro in &intros {
+        output.push_str(intro);
+        output.push_str("\n\n");
+    }
+    for (heading, items) in &lists {
+        output.push_str(&format!("### {heading}\n"));
+        let mut number = 0;
+        for item in items {
+            if is_numbered(item) {
+                number += 1;
+                output.push_str(&format!("{number}. {}\n", list_item_text(item)));
+            } else {
+                output.push_str(item);
+                output.push('\n');
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// A list line without its `- ` or `1. ` marker
+fn list_item_text(line: &str) -> &str {
+    if is_numbered(line) {
+        line.split_once(". ").map_or(line, |(_, rest)| rest)
+    } else {
+        line.strip_prefix("- ").unwrap_or(line)
+    }
+}
+
+fn is_numbered(line: &str) -> bool {
+    line.split_once(". ")
+        .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
 const DEVELOPER_PREFIX: &str = r#"## Role: Software Developer
 
 You are a skilled software developer focused on writing clean, functional, and maintainable code.
@@ -260,3 +487,65 @@ You are a DevOps engineer focused on CI/CD, infrastructure, deployment, and oper
 - Health checks, readiness probes, and graceful shutdown
 
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> Config {
+        toml::from_str(toml).expect("valid config")
+    }
+
+    #[test]
+    fn merged_prefixes_drop_repeated_items_and_renumber() {
+        let a = "## Role: A\n\nIntro A.\n\n### Priorities\n1. **Correctness** — first\n2. Speed\n\n### Avoid\n- Magic numbers\n";
+        let b = "## Role: B\n\nIntro B.\n\n### Priorities\n1. Speed\n2. Safety\n\n### Avoid\n- Magic numbers\n- Globals\n";
+        assert_eq!(
+            merge_prefixes(&[a, b]),
+            "## Role: A + B\n\nIntro A.\n\nIntro B.\n\n\
+             ### Priorities\n1. **Correctness** — first\n2. Speed\n3. Safety\n\n\
+             ### Avoid\n- Magic numbers\n- Globals\n\n"
+        );
+    }
+
+    #[test]
+    fn repeated_role_is_merged_once() {
+        let persona = Persona::resolve("senior+security+senior", None).unwrap();
+        assert_eq!(persona.name, "senior+security");
+        assert_eq!(persona.roles, ["senior", "security"]);
+        assert_eq!(persona.prefix, Persona::resolve("senior+security", None).unwrap().prefix);
+
+        // An alias names the same role
+        assert_eq!(Persona::resolve("senior+architect", None).unwrap().name, "senior");
+    }
+
+    #[test]
+    fn alias_cycle_is_an_error() {
+        let config = config(
+            r#"
+[roles.backend]
+aliases = ["be"]
+combine = ["senior", "platform"]
+
+[roles.platform]
+combine = ["BE"]
+"#,
+        );
+        let err = Persona::resolve("platform", Some(&config)).unwrap_err();
+        assert!(err.contains("combines itself"), "{err}");
+        assert!(Persona::resolve("be+security", Some(&config)).is_err());
+    }
+
+    #[test]
+    fn unknown_role_inside_combine_is_reported() {
+        let config = config(
+            r#"
+[roles.backend]
+combine = ["senior", "wizard"]
+"#,
+        );
+        let err = Persona::resolve("backend", Some(&config)).unwrap_err();
+        assert!(err.contains("unknown role: 'wizard'"), "{err}");
+        assert!(err.contains("backend"), "{err}");
+    }
+}
@@ -0,0 +1,225 @@
+//! Context cost of the files promptctl has installed in a project.
+//!
+//! Each agent's base instruction file is loaded on every request. Hook files
+//! load either always or only when a matching file is in context: Cursor rules
+//! by `globs` unless `alwaysApply` is set, Windsurf rules by their `trigger`,
+//! Copilot instructions by `applyTo`, and Claude's skillset through its
+//! SessionStart hook, or as a skill the model loads on demand. Cline and Roo
+//! Code load every rule file, and Roo's mode rules only in that mode. Gemini
+//! CLI's skillsets are imported into GEMINI.md.
+
+use crate::agents::{self, Agent};
+use crate::prompt_builder::section_breakdown;
+use crate::tokens;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Number of largest sections reported per file
+const LARGEST_SECTIONS: usize = 3;
+
+/// Installed files of one agent and their token counts
+#[derive(Debug, Serialize)]
+pub struct AgentStats {
+    pub agent: &'static str,
+    pub tokenizer: &'static str,
+    pub files: Vec<FileStats>,
+}
+
+impl AgentStats {
+    /// Tokens loaded into every request
+    pub fn always_tokens(&self) -> usize {
+        self.files.iter().filter(|f| f.always).map(|f| f.tokens).sum()
+    }
+
+    /// Tokens loaded when every hook matches
+    pub fn total_tokens(&self) -> usize {
+        self.files.iter().map(|f| f.tokens).sum()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileStats {
+    /// Path relative to the project root
+    pub path: String,
+    pub tokens: usize,
+    /// Whether the file is loaded regardless of which files are in context
+    pub always: bool,
+    /// When the agent loads the file, e.g. "always" or "applyTo **/*.rs"
+    pub loads: String,
+    pub largest_sections: Vec<SectionStats>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SectionStats {
+    pub title: String,
+    pub tokens: usize,
+}
+
+/// Collect stats for every agent with promptctl files under `root`.
+///
+/// Claude's SessionStart hook runs `promptctl show …` at startup; `show`
+/// renders that output from the hook's arguments so it can be counted.
+pub fn collect(root: &Path, show: &dyn Fn(&[String]) -> Option<String>) -> Vec<AgentStats> {
+    let hooks = agents::list_agent_hooks(root);
+    let mut results = Vec::new();
+
+    for &agent in Agent::all() {
+        let counter = tokens::for_agent(agent);
+        let mut files = Vec::new();
+
+        if let Some(base) = agent.resolve_path(root, false)
+            && let Ok(text) = fs::read_to_string(&base)
+            && text.contains("promptctl")
+        {
+            files.push(file_stats(root, &base, &text, counter, "always".into(), true));
+        }
+
+        let mut hook_paths: Vec<_> = hooks
+            .iter()
+            .filter(|(a, _)| *a == agent)
+            .flat_map(|(_, paths)| paths.iter())
+            .collect();
+        hook_paths.sort();
+        for path in hook_paths {
+            let Ok(text) = fs::read_to_string(path) else {
+                continue;
+            };
+            match agent {
+                Agent::Claude if path.ends_with("SKILL.md") => {
+                    let context = skill_context(path, &text);
+                    let loads = "skill, on demand".to_string();
+                    files.push(file_stats(root, path, &context, counter, loads, false));
+                }
+                Agent::Claude => {
+                    for args in session_start_calls(path, &text) {
+                        let Some(context) = show(&args) else {
+                            continue;
+                        };
+                        let loads = format!("SessionStart: promptctl {}", args.join(" "));
+                        files.push(file_stats(root, path, &context, counter, loads, true));
+                    }
+                }
+                Agent::Cursor => {
+                    let always = frontmatter(&text, "alwaysApply") == Some("true");
+                    let loads = if always {
+                        "alwaysApply".to_string()
+                    } else {
+                        format!("globs {}", frontmatter(&text, "globs").unwrap_or_default())
+                    };
+                    files.push(file_stats(root, path, &text, counter, loads, always));
+                }
+                Agent::Windsurf => {
+                    let trigger = frontmatter(&text, "trigger").unwrap_or_default();
+                    let always = trigger == "always_on";
+                    let loads = match trigger {
+                        "glob" => format!("glob {}", frontmatter(&text, "globs").unwrap_or_default()),
+                        other => other.replace('_', " "),
+                    };
+                    files.push(file_stats(root, path, &text, counter, loads, always));
+                }
+                Agent::Roo if path.ends_with("promptctl.md") => {
+                    let mode = path
+                        .parent()
+                        .and_then(|d| d.file_name())
+                        .map(|n| n.to_string_lossy().trim_start_matches("rules-").to_string())
+                        .unwrap_or_default();
+                    let loads = format!("mode {mode}");
+                    files.push(file_stats(root, path, &text, counter, loads, false));
+                }
+                Agent::Gemini => {
+                    let loads = "imported by GEMINI.md".to_string();
+                    files.push(file_stats(root, path, &text, counter, loads, true));
+                }
+                Agent::Cline | Agent::Roo => {
+                    files.push(file_stats(root, path, &text, counter, "always".into(), true));
+                }
+                Agent::Copilot => {
+                    let apply_to = frontmatter(&text, "applyTo").unwrap_or_default();
+                    let always = matches!(apply_to, "**" | "**/*");
+                    let loads = format!("applyTo {apply_to}");
+                    files.push(file_stats(root, path, &text, counter, loads, always));
+                }
+                _ => {}
+            }
+        }
+
+        if !files.is_empty() {
+            results.push(AgentStats {
+                agent: agent.name(),
+                tokenizer: counter.name(),
+                files,
+            });
+        }
+    }
+
+    results
+}
+
+fn file_stats(
+    root: &Path,
+    path: &Path,
+    text: &str,
+    counter: &dyn tokens::TokenCounter,
+    loads: String,
+    always: bool,
+) -> FileStats {
+    let mut sections = section_breakdown(text, counter);
+    sections.sort_by_key(|(_, tokens)| std::cmp::Reverse(*tokens));
+    FileStats {
+        path: path.strip_prefix(root).unwrap_or(path).display().to_string(),
+        tokens: counter.count(text),
+        always,
+        loads,
+        largest_sections: sections
+            .into_iter()
+            .take(LARGEST_SECTIONS)
+            .map(|(title, tokens)| SectionStats { title, tokens })
+            .collect(),
+    }
+}
+
+/// `SKILL.md` followed by the supporting files it links, which the model reads
+/// once the skill is loaded.
+fn skill_context(path: &Path, skill: &str) -> String {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut context = skill.to_string();
+    for line in skill.lines() {
+        if let Some(link) = line.strip_prefix("- [")
+            && let Some((_, file)) = link.split_once("](")
+            && let Ok(text) = fs::read_to_string(dir.join(file.trim_end_matches(')')))
+        {
+            context.push_str("\n\n");
+            context.push_str(&text);
+        }
+    }
+    context
+}
+
+/// Arguments of each `promptctl show <lang> …` call in Claude's SessionStart
+/// script, one per detected language.
+fn session_start_calls(path: &Path, script: &str) -> Vec<Vec<String>> {
+    if path.file_name().is_none_or(|n| n != "promptctl-session-start.sh") {
+        return Vec::new();
+    }
+    script
+        .split("$(promptctl ")
+        .skip(1)
+        .filter_map(|call| call.split(" 2>").next())
+        .map(|call| {
+            call.split_whitespace()
+                .map(|word| word.trim_matches('\'').to_string())
+                .collect()
+        })
+        .collect()
+}
+
+/// Value of a `key: value` line in a file's leading `---` frontmatter block.
+fn frontmatter<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    let body = text.strip_prefix("---\n")?;
+    let block = &body[..body.find("\n---")?];
+    block.lines().find_map(|line| {
+        let value = line.strip_prefix(key)?.strip_prefix(':')?;
+        Some(value.trim().trim_matches('"'))
+    })
+}
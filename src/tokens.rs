@@ -0,0 +1,88 @@
+//! Token counting for budgets and reports.
+//!
+//! Without the `tokenizer` feature every count is the ~4 characters per token
+//! estimate. With it, text is run through the BPE vocabulary of the agent's
+//! model family, bundled in the binary so counting works offline.
+
+use crate::agents::Agent;
+
+/// Counts the tokens a piece of text costs an agent.
+pub trait TokenCounter: Sync {
+    fn count(&self, text: &str) -> usize;
+
+    /// Short label shown next to counts, e.g. "estimate" or "o200k_base"
+    fn name(&self) -> &'static str;
+}
+
+/// Rough estimate: ~4 characters per token
+pub struct Estimate;
+
+impl TokenCounter for Estimate {
+    fn count(&self, text: &str) -> usize {
+        text.len() / 4
+    }
+
+    fn name(&self) -> &'static str {
+        "estimate"
+    }
+}
+
+/// A bundled BPE vocabulary.
+#[cfg(feature = "tokenizer")]
+pub struct Bpe {
+    name: &'static str,
+    bpe: fn() -> &'static tiktoken_rs::CoreBPE,
+}
+
+#[cfg(feature = "tokenizer")]
+impl TokenCounter for Bpe {
+    fn count(&self, text: &str) -> usize {
+        (self.bpe)().encode_with_special_tokens(text).len()
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// GPT-4o family vocabulary (Copilot, Cursor and Codex defaults)
+#[cfg(feature = "tokenizer")]
+static O200K: Bpe = Bpe {
+    name: "o200k_base",
+    bpe: tiktoken_rs::o200k_base_singleton,
+};
+
+/// GPT-4 family vocabulary. Claude's tokenizer is not published and Gemini's
+/// is not bundled; cl100k_base is the closest approximation and is used for
+/// model-agnostic agents too.
+#[cfg(feature = "tokenizer")]
+static CL100K: Bpe = Bpe {
+    name: "cl100k_base",
+    bpe: tiktoken_rs::cl100k_base_singleton,
+};
+
+/// The counter matching an agent's model family.
+#[cfg(feature = "tokenizer")]
+pub fn for_agent(agent: Agent) -> &'static dyn TokenCounter {
+    match agent {
+        Agent::Copilot | Agent::Cursor | Agent::Codex => &O200K,
+        Agent::Claude
+        | Agent::Windsurf
+        | Agent::Cline
+        | Agent::Roo
+        | Agent::Gemini
+        | Agent::Aider
+        | Agent::Raw => &CL100K,
+    }
+}
+
+/// The counter matching an agent's model family.
+#[cfg(not(feature = "tokenizer"))]
+pub fn for_agent(_agent: Agent) -> &'static dyn TokenCounter {
+    &Estimate
+}
+
+/// Counter for agent-independent figures such as size tier budgets.
+pub fn default_counter() -> &'static dyn TokenCounter {
+    for_agent(Agent::Raw)
+}